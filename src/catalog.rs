@@ -0,0 +1,294 @@
+use crate::{ErrorKind, FriendlyErrorError};
+use std::collections::HashMap;
+
+/// Declares [`Message`] from one table of variants, catalog keys and English
+/// texts, so the list of messages can't get out of sync with the enum.
+macro_rules! messages {
+    ($($(#[$attr:meta])* $message:ident => $key:literal, $english:literal;)*) => {
+        /// A fixed piece of text printed in or around diagnostics.
+        /// Placeholders such as `{url}` are filled in when the message is
+        /// used. Messages ending in `One` are used for a count of exactly one,
+        /// and those ending in `Other` for every other count.
+        #[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+        pub enum Message {
+            $($(#[$attr])* $message,)*
+        }
+
+        const MESSAGES: &[Message] = &[$(Message::$message,)*];
+
+        impl Message {
+            /// The key used for this message in catalog files.
+            pub fn get_key(&self) -> &'static str {
+                match self {
+                    $(Message::$message => $key,)*
+                }
+            }
+
+            pub fn get_english(&self) -> &'static str {
+                match self {
+                    $(Message::$message => $english,)*
+                }
+            }
+        }
+    };
+}
+
+messages! {
+    ErrorLabel => "error-label", "Error";
+    WarningLabel => "warning-label", "Warning";
+    ImprovementLabel => "improvement-label", "Improvement";
+    CodeStyleLabel => "code-style-label", "Code style";
+    /// `{url}` is replaced by the link.
+    DocsLink => "docs-link", "To learn more, read the docs at {url}";
+    /// `{label}` is the error's original kind.
+    WarnedByConfiguration => "warned-by-configuration", "{label} warned by configuration.";
+    /// `{label}` is the error's original kind.
+    DeniedByConfiguration => "denied-by-configuration", "{label} denied by configuration.";
+    /// `{label}` is the error's original kind.
+    ForbiddenByConfiguration => "forbidden-by-configuration", "{label} forbidden by configuration.";
+    ErrorCountOne => "error-count-one", "{count} error";
+    ErrorCountOther => "error-count-other", "{count} errors";
+    WarningCountOne => "warning-count-one", "{count} warning";
+    WarningCountOther => "warning-count-other", "{count} warnings";
+    ImprovementCountOne => "improvement-count-one", "{count} improvement";
+    ImprovementCountOther => "improvement-count-other", "{count} improvements";
+    CodeStyleCountOne => "code-style-count-one", "{count} code style issue";
+    CodeStyleCountOther => "code-style-count-other", "{count} code style issues";
+    /// `{counts}` lists the counts, separated by commas.
+    SummaryFooter => "summary-footer", "{counts} emitted";
+    /// `{count}` errors were shown and `{more}` diagnostics weren't.
+    AbortingOne => "aborting-one", "aborting after {count} error; {more} more not shown";
+    AbortingOther => "aborting-other", "aborting after {count} errors; {more} more not shown";
+    /// `{count}` is how often the diagnostic was reported.
+    ReportedTimes => "reported-times", "(reported {count} times)";
+    UnusedSuppressionTitle => "unused-suppression-title", "unused suppression";
+    /// `{code}` is the suppressed error code.
+    UnusedSuppression => "unused-suppression", "No {code} diagnostic was reported here.";
+    /// For suppression comments without codes.
+    UnusedSuppressionAny => "unused-suppression-any", "No diagnostic was reported here.";
+}
+
+impl Message {
+    pub fn label(kind: ErrorKind) -> Self {
+        match kind {
+            ErrorKind::Error => Message::ErrorLabel,
+            ErrorKind::Warning => Message::WarningLabel,
+            ErrorKind::Improvement => Message::ImprovementLabel,
+            ErrorKind::CodeStyle => Message::CodeStyleLabel,
+        }
+    }
+
+    /// The message for `count` diagnostics of `kind`, e.g. `{count} errors`.
+    pub fn count(kind: ErrorKind, count: usize) -> Self {
+        match (kind, count == 1) {
+            (ErrorKind::Error, true) => Message::ErrorCountOne,
+            (ErrorKind::Error, false) => Message::ErrorCountOther,
+            (ErrorKind::Warning, true) => Message::WarningCountOne,
+            (ErrorKind::Warning, false) => Message::WarningCountOther,
+            (ErrorKind::Improvement, true) => Message::ImprovementCountOne,
+            (ErrorKind::Improvement, false) => Message::ImprovementCountOther,
+            (ErrorKind::CodeStyle, true) => Message::CodeStyleCountOne,
+            (ErrorKind::CodeStyle, false) => Message::CodeStyleCountOther,
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        MESSAGES
+            .iter()
+            .find(|message| message.get_key() == key)
+            .copied()
+    }
+}
+
+/// Translations of every [`Message`]. Messages that aren't translated fall
+/// back to English.
+#[derive(PartialEq, Debug, Clone)]
+pub struct MessageCatalog {
+    messages: HashMap<Message, String>,
+}
+
+impl MessageCatalog {
+    pub fn new() -> Self {
+        MessageCatalog {
+            messages: HashMap::new(),
+        }
+    }
+
+    /// Reads a catalog with one `key = value` pair per line, using the keys
+    /// from [`Message::get_key`]. Blank lines and lines starting with `#` are
+    /// skipped.
+    ///
+    /// ```text
+    /// # German
+    /// error-label = Fehler
+    /// docs-link = Mehr dazu in der Dokumentation unter {url}
+    /// ```
+    pub fn parse(source: &str) -> Result<Self, FriendlyErrorError> {
+        let mut catalog = MessageCatalog::new();
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| {
+                FriendlyErrorError::CatalogError(format!(
+                    "line {}: expected `key = value`",
+                    index + 1
+                ))
+            })?;
+            let message = Message::from_key(key.trim()).ok_or_else(|| {
+                FriendlyErrorError::CatalogError(format!(
+                    "line {}: unknown message `{}`",
+                    index + 1,
+                    key.trim()
+                ))
+            })?;
+            catalog = catalog.set(message, value.trim());
+        }
+        Ok(catalog)
+    }
+
+    pub fn set<S: Into<String>>(mut self, message: Message, text: S) -> Self {
+        self.messages.insert(message, text.into());
+        self
+    }
+
+    pub fn get(&self, message: Message) -> &str {
+        self.messages
+            .get(&message)
+            .map_or(message.get_english(), String::as_str)
+    }
+
+    /// The message with every `{name}` replaced by its value in `args`.
+    /// Placeholders inside the values are left as they are, as are names that
+    /// aren't in `args`.
+    pub fn format(&self, message: Message, args: &[(&str, &str)]) -> String {
+        let mut rest = self.get(message);
+        let mut text = String::with_capacity(rest.len());
+        while let Some(start) = rest.find('{') {
+            text.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let placeholder = rest.find('}').and_then(|end| {
+                let (_, value) = args.iter().find(|(name, _)| *name == &rest[1..end])?;
+                Some((value, end))
+            });
+            match placeholder {
+                Some((value, end)) => {
+                    text.push_str(value);
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    text.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        text.push_str(rest);
+        text
+    }
+
+    pub(crate) fn get_label(&self, kind: ErrorKind) -> &str {
+        self.get(Message::label(kind))
+    }
+
+    pub(crate) fn format_docs_link(&self, url: &str) -> String {
+        self.format(Message::DocsLink, &[("url", url)])
+    }
+}
+
+impl Default for MessageCatalog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn english_fallback_test() {
+        let catalog = MessageCatalog::new().set(Message::ErrorLabel, "Fehler");
+        assert_eq!(catalog.get_label(ErrorKind::Error), "Fehler");
+        assert_eq!(catalog.get_label(ErrorKind::CodeStyle), "Code style");
+        assert_eq!(
+            catalog.format_docs_link("https://example.com/"),
+            "To learn more, read the docs at https://example.com/"
+        );
+    }
+
+    #[test]
+    fn format_test() {
+        let catalog = MessageCatalog::new().set(Message::ReportedTimes, "({count}-mal gemeldet)");
+        assert_eq!(
+            catalog.format(Message::ReportedTimes, &[("count", "3")]),
+            "(3-mal gemeldet)"
+        );
+        assert_eq!(
+            MessageCatalog::new().format(Message::ErrorCountOther, &[("count", "2")]),
+            "2 errors"
+        );
+    }
+
+    #[test]
+    fn format_single_pass_test() {
+        let catalog = MessageCatalog::new().set(Message::AbortingOther, "{count} {more} {other} {");
+        assert_eq!(
+            catalog.format(
+                Message::AbortingOther,
+                &[("count", "{more}"), ("more", "3")]
+            ),
+            "{more} 3 {other} {"
+        );
+        assert_eq!(
+            MessageCatalog::new()
+                .format(Message::DocsLink, &[("url", "https://example.com/{url}")]),
+            "To learn more, read the docs at https://example.com/{url}"
+        );
+    }
+
+    #[test]
+    fn keys_are_unique_test() {
+        for (index, message) in MESSAGES.iter().enumerate() {
+            assert_eq!(Message::from_key(message.get_key()), Some(*message));
+            assert!(!MESSAGES[..index].contains(message));
+        }
+    }
+
+    #[test]
+    fn parse_test() {
+        let catalog = MessageCatalog::parse(indoc! {"
+            # German
+            error-label = Fehler
+
+            docs-link = Mehr dazu in der Dokumentation unter {url}
+        "})
+        .unwrap();
+        assert_eq!(
+            catalog,
+            MessageCatalog::new()
+                .set(Message::ErrorLabel, "Fehler")
+                .set(
+                    Message::DocsLink,
+                    "Mehr dazu in der Dokumentation unter {url}"
+                )
+        );
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            MessageCatalog::parse("error-label = Fehler\nwarning-label"),
+            Err(FriendlyErrorError::CatalogError(
+                "line 2: expected `key = value`".to_string()
+            ))
+        );
+        assert_eq!(
+            MessageCatalog::parse("error-lable = Fehler"),
+            Err(FriendlyErrorError::CatalogError(
+                "line 1: unknown message `error-lable`".to_string()
+            ))
+        );
+    }
+}
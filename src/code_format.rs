@@ -0,0 +1,125 @@
+use crate::{FriendlyError, FriendlyErrorError};
+
+#[derive(PartialEq, Debug, Clone)]
+enum CodePattern {
+    Prefix { prefix: String, digits: usize },
+    Pattern(String),
+}
+
+impl CodePattern {
+    fn matches(&self, code: &str) -> bool {
+        match self {
+            CodePattern::Prefix { prefix, digits } => match code.strip_prefix(prefix.as_str()) {
+                Some(rest) => rest.len() == *digits && rest.chars().all(|c| c.is_ascii_digit()),
+                None => false,
+            },
+            CodePattern::Pattern(pattern) => {
+                pattern.chars().count() == code.chars().count()
+                    && pattern
+                        .chars()
+                        .zip(code.chars())
+                        .all(|(expected, c)| match expected {
+                            '#' => c.is_ascii_digit(),
+                            '@' => c.is_ascii_alphabetic(),
+                            _ => expected == c,
+                        })
+            }
+        }
+    }
+}
+
+/// The shapes error codes may take. A code is valid if it matches any of
+/// them; without any, every code is valid.
+#[derive(PartialEq, Debug, Clone)]
+pub struct CodeFormat {
+    patterns: Vec<CodePattern>,
+}
+
+impl CodeFormat {
+    pub fn new() -> Self {
+        CodeFormat {
+            patterns: Vec::new(),
+        }
+    }
+
+    /// Accepts `prefix` followed by exactly `digits` digits, e.g. `E1234` for
+    /// `.prefix("E", 4)`.
+    pub fn prefix<S: Into<String>>(mut self, prefix: S, digits: usize) -> Self {
+        self.patterns.push(CodePattern::Prefix {
+            prefix: prefix.into(),
+            digits,
+        });
+        self
+    }
+
+    /// Accepts codes that match `pattern` character for character, where `#`
+    /// matches any digit and `@` any ASCII letter, e.g. `@@-###`.
+    pub fn pattern<S: Into<String>>(mut self, pattern: S) -> Self {
+        self.patterns.push(CodePattern::Pattern(pattern.into()));
+        self
+    }
+
+    pub fn matches(&self, code: &str) -> bool {
+        self.patterns.is_empty() || self.patterns.iter().any(|pattern| pattern.matches(code))
+    }
+
+    /// Fails with [`FriendlyErrorError::InvalidErrorCode`] if `error` has a
+    /// code that doesn't match.
+    pub fn validate(&self, error: &FriendlyError) -> Result<(), FriendlyErrorError> {
+        match error.get_error_code() {
+            Some(code) if !self.matches(code) => {
+                Err(FriendlyErrorError::InvalidErrorCode(code.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for CodeFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn prefix_test() {
+        let format = CodeFormat::new().prefix("E", 4).prefix("W", 3);
+        assert!(format.matches("E1234"));
+        assert!(format.matches("W123"));
+        assert!(!format.matches("E123"));
+        assert!(!format.matches("W1234"));
+        assert!(!format.matches("E12a4"));
+        assert!(!format.matches("X1234"));
+    }
+
+    #[test]
+    fn pattern_test() {
+        let format = CodeFormat::new().pattern("@@-###");
+        assert!(format.matches("TS-001"));
+        assert!(!format.matches("TS001"));
+        assert!(!format.matches("T1-001"));
+        assert!(!format.matches("TS-0001"));
+    }
+
+    #[test]
+    fn validate_test() {
+        let format = CodeFormat::new().prefix("E", 4);
+        assert_eq!(format.validate(&FriendlyError::new()), Ok(()));
+        assert_eq!(
+            format.validate(&FriendlyError::new().error_code("E1234")),
+            Ok(())
+        );
+        assert_eq!(
+            format.validate(&FriendlyError::new().error_code("E12345")),
+            Err(FriendlyErrorError::InvalidErrorCode("E12345".to_string()))
+        );
+        assert_eq!(
+            CodeFormat::new().validate(&FriendlyError::new().error_code("anything")),
+            Ok(())
+        );
+    }
+}
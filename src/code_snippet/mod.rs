@@ -1,20 +1,23 @@
+mod position;
+
+pub use position::SpanPosition;
+
+use crate::RenderConfig;
 use std::cmp::max;
+use std::fmt;
 
 #[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum HighlightKind {
     Error,
     Warning,
     Info,
 }
 
-#[derive(PartialEq, Debug, Clone, Copy)]
-enum CalculatedFieldError {
-    NotCalculated,
-    Invalid,
-}
-
-type CalculatedFieldResult<T> = Result<T, CalculatedFieldError>;
-
 #[derive(PartialEq, Debug, Clone)]
 pub struct FriendlyCodeSnippet {
     file_contents: String,
@@ -25,11 +28,15 @@ pub struct FriendlyCodeSnippet {
     line_end: Option<usize>,
     kind: HighlightKind,
     caption: Option<String>,
+}
 
-    // private fields
-    line_start_start_index: CalculatedFieldResult<usize>,
-    line_end_start_index: CalculatedFieldResult<usize>,
-    indent_size: CalculatedFieldResult<usize>,
+/// What [`FriendlyCodeSnippet::validate_inputs`] works out while validating a
+/// snippet, kept apart from it so rendering doesn't copy the file contents.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(crate) struct SnippetLayout {
+    line_start_start_index: usize,
+    line_end_start_index: usize,
+    indent_size: usize,
 }
 
 #[derive(PartialEq, Debug, Clone, Copy)]
@@ -49,17 +56,46 @@ fn get_digit_count(mut number: usize) -> usize {
     digits
 }
 
-fn get_line_number_prefix(line_number: usize, indent: usize) -> String {
+/// The character index where `line` starts in `contents`.
+fn get_line_start_index(contents: &str, line: usize) -> Option<usize> {
+    let mut line_count = 1;
+    for (index, char) in contents.chars().enumerate() {
+        if line_count == line {
+            return Some(index);
+        }
+        if char == '\n' {
+            line_count += 1;
+        }
+    }
+    None
+}
+
+fn get_indent_size(line_start_start_index: usize, line_end_start_index: usize) -> usize {
+    let longest_line_number = max(line_start_start_index, line_end_start_index);
+    let default_indent_size = 4;
+    max(
+        get_digit_count(longest_line_number) + 1,
+        default_indent_size,
+    )
+}
+
+fn get_line_number_prefix(line_number: usize, indent: usize, config: &RenderConfig) -> String {
     let number = line_number.to_string();
+    let theme = config.get_theme();
     let mut output = " ".repeat(indent - 1 - number.len());
-    output.push_str(&number);
-    output.push_str(" | ");
+    output.push_str(&config.paint(&number, theme.get_line_number()));
+    output.push(' ');
+    let gutter = config.glyph_table().gutter.to_string();
+    output.push_str(&config.paint(&gutter, theme.get_gutter()));
+    output.push(' ');
     output
 }
 
-fn get_blank_line_prefix(indent: usize) -> String {
+fn get_blank_line_prefix(indent: usize, config: &RenderConfig) -> String {
     let mut output = " ".repeat(indent);
-    output.push_str("| ");
+    let gutter = config.glyph_table().gutter.to_string();
+    output.push_str(&config.paint(&gutter, config.get_theme().get_gutter()));
+    output.push(' ');
     output
 }
 
@@ -74,11 +110,6 @@ impl FriendlyCodeSnippet {
             line_end: None,
             kind: HighlightKind::Error,
             caption: None,
-
-            // private fields
-            line_start_start_index: Err(CalculatedFieldError::NotCalculated),
-            line_end_start_index: Err(CalculatedFieldError::NotCalculated),
-            indent_size: Err(CalculatedFieldError::NotCalculated),
         }
     }
 
@@ -117,98 +148,82 @@ impl FriendlyCodeSnippet {
         self
     }
 
-    pub(crate) fn calc_line_start_start_index(&mut self) {
-        match self.line_start {
-            Some(line) => {
-                let mut line_count = 1;
-                for (index, char) in self.file_contents.chars().enumerate() {
-                    if line_count == line {
-                        self.line_start_start_index = Ok(index);
-                        return;
-                    }
-                    if char == '\n' {
-                        line_count += 1;
-                    }
-                }
-                self.line_start_start_index = Err(CalculatedFieldError::Invalid);
-            }
-            None => {
-                self.line_start_start_index = Err(CalculatedFieldError::Invalid);
-            }
-        }
+    pub fn get_file_contents(&self) -> &str {
+        &self.file_contents
     }
 
-    pub(crate) fn calc_line_end_start_index(&mut self) {
-        match self.line_end {
-            Some(line) => {
-                let mut line_count = 1;
-                for (index, char) in self.file_contents.chars().enumerate() {
-                    if line_count == line {
-                        self.line_end_start_index = Ok(index);
-                        return;
-                    }
-                    if char == '\n' {
-                        line_count += 1;
-                    }
-                }
-                self.line_end_start_index = Err(CalculatedFieldError::Invalid);
-            }
-            None => {
-                self.line_end_start_index = Err(CalculatedFieldError::Invalid);
-            }
-        }
+    pub fn get_file_path(&self) -> Option<&str> {
+        self.file_path.as_deref()
+    }
+
+    pub fn get_index_start(&self) -> Option<usize> {
+        self.index_start
+    }
+
+    pub fn get_index_end(&self) -> Option<usize> {
+        self.index_end
+    }
+
+    pub fn get_line_start(&self) -> Option<usize> {
+        self.line_start
+    }
+
+    pub fn get_line_end(&self) -> Option<usize> {
+        self.line_end
+    }
+
+    pub fn get_kind(&self) -> HighlightKind {
+        self.kind
     }
 
-    pub(crate) fn validate_inputs(&self) -> Result<bool, FriendlyCodeSnippetError> {
+    pub fn get_caption(&self) -> Option<&str> {
+        self.caption.as_deref()
+    }
+
+    pub(crate) fn calc_line_start_start_index(&self) -> Option<usize> {
+        get_line_start_index(&self.file_contents, self.line_start?)
+    }
+
+    pub(crate) fn calc_line_end_start_index(&self) -> Option<usize> {
+        get_line_start_index(&self.file_contents, self.line_end?)
+    }
+
+    pub(crate) fn validate_inputs(&self) -> Result<SnippetLayout, FriendlyCodeSnippetError> {
         if self.line_start.is_none() && self.index_start.is_none() {
             return Err(FriendlyCodeSnippetError::MissingStartPosition);
         }
         if self.line_end.is_none() && self.index_end.is_none() {
             return Err(FriendlyCodeSnippetError::MissingEndPosition);
         }
-        match self.line_start_start_index {
-            Err(CalculatedFieldError::Invalid) => {
-                return Err(FriendlyCodeSnippetError::InvalidStartPosition)
-            }
-            Err(CalculatedFieldError::NotCalculated) => {
-                panic!("line_start_start_index must be calculated before inputs are validated")
-            }
-            Ok(_) => {}
-        }
-        match self.line_end_start_index {
-            Err(CalculatedFieldError::Invalid) => {
-                return Err(FriendlyCodeSnippetError::InvalidEndPosition)
-            }
-            Err(CalculatedFieldError::NotCalculated) => {
-                panic!("line_end_start_index must be calculated before inputs are validated")
-            }
-            Ok(_) => {}
-        }
-        if self.line_start_start_index.unwrap() > self.line_end_start_index.unwrap() {
+        let line_start_start_index = self
+            .calc_line_start_start_index()
+            .ok_or(FriendlyCodeSnippetError::InvalidStartPosition)?;
+        let line_end_start_index = self
+            .calc_line_end_start_index()
+            .ok_or(FriendlyCodeSnippetError::InvalidEndPosition)?;
+        if line_start_start_index > line_end_start_index {
             return Err(FriendlyCodeSnippetError::InvalidEndPosition);
         }
-        if self.line_start_start_index.unwrap() == self.line_end_start_index.unwrap()
-            && self.index_start.unwrap() >= self.index_end.unwrap()
-        {
-            return Err(FriendlyCodeSnippetError::InvalidEndPosition);
+        if line_start_start_index == line_end_start_index {
+            let index_start = self
+                .index_start
+                .ok_or(FriendlyCodeSnippetError::MissingStartPosition)?;
+            let index_end = self
+                .index_end
+                .ok_or(FriendlyCodeSnippetError::MissingEndPosition)?;
+            if index_start >= index_end {
+                return Err(FriendlyCodeSnippetError::InvalidEndPosition);
+            }
         }
-        Ok(true)
+        Ok(SnippetLayout {
+            line_start_start_index,
+            line_end_start_index,
+            indent_size: get_indent_size(line_start_start_index, line_end_start_index),
+        })
     }
 
-    pub(crate) fn calc_indent_size(&mut self) {
-        let longest_line_number = max(
-            self.line_start_start_index.unwrap(),
-            self.line_end_start_index.unwrap(),
-        );
-        let default_indent_size = 4;
-        self.indent_size = Ok(max(
-            get_digit_count(longest_line_number) + 1,
-            default_indent_size,
-        ));
-    }
-
-    pub(crate) fn build_file_url(&self) -> String {
-        let mut output = " ".repeat(self.indent_size.unwrap());
+    pub(crate) fn build_file_url(&self, indent_size: usize, config: &RenderConfig) -> String {
+        let mut output = String::new();
         let mut has_contents = false;
         if let Some(file_path) = &self.file_path {
             output.push_str(file_path);
@@ -231,104 +246,177 @@ impl FriendlyCodeSnippet {
         if !has_contents {
             return String::new();
         }
-        output.push('\n');
-        output
+        let mut file_url = " ".repeat(indent_size);
+        file_url.push_str(&config.paint(&output, config.get_theme().get_dimmed()));
+        file_url.push('\n');
+        file_url
     }
 
-    pub(crate) fn build_lines(&self) -> String {
+    pub(crate) fn build_lines(&self, layout: &SnippetLayout, config: &RenderConfig) -> String {
         let mut output = String::new();
-        if self.line_start_start_index.unwrap() == self.line_end_start_index.unwrap() {
+        if layout.line_start_start_index == layout.line_end_start_index {
             output.push_str(&get_line_number_prefix(
                 self.line_start.unwrap(),
-                self.indent_size.unwrap(),
+                layout.indent_size,
+                config,
             ));
-            let mut index = self.line_start_start_index.unwrap();
-            while index < self.file_contents.len() && !self.file_contents[index..index + 1].eq("\n")
-            {
-                index += 1;
-            }
-            let line_contents = &self.file_contents[self.line_start_start_index.unwrap()..index];
+            let line_contents = self
+                .file_contents
+                .split('\n')
+                .nth(self.line_start.unwrap() - 1)
+                .unwrap_or_default();
             output.push_str(line_contents);
             output.push('\n');
-            output.push_str(&get_blank_line_prefix(self.indent_size.unwrap()));
+            output.push_str(&get_blank_line_prefix(layout.indent_size, config));
             output.push_str(&" ".repeat(self.index_start.unwrap()));
-            output.push_str(&"^".repeat(self.index_end.unwrap() - self.index_start.unwrap()));
+            let underline = config.glyph_table().underline.to_string();
+            output.push_str(&config.paint(
+                &underline.repeat(self.index_end.unwrap() - self.index_start.unwrap()),
+                config.get_theme().get_highlight_kind(self.kind),
+            ));
             output.push('\n')
+        } else {
+            output.push_str(&self.build_multiline(layout.indent_size, config));
+        }
+
+        output
+    }
+
+    /// Draws a span covering several lines, connecting its start and end
+    /// with a line down the left-hand side of the code:
+    ///
+    /// ```text
+    ///   1 |   fn main() {
+    ///     |  ___________^
+    ///   2 | |     println!("Hello, world!");
+    ///   3 | | }
+    ///     | |_^
+    /// ```
+    fn build_multiline(&self, indent: usize, config: &RenderConfig) -> String {
+        let glyphs = config.glyph_table();
+        let style = config.get_theme().get_highlight_kind(self.kind);
+        let line_start = self.line_start.unwrap();
+        let line_end = self.line_end.unwrap();
+        let lines: Vec<&str> = self.file_contents.split('\n').collect();
+        let index_start = self.index_start.unwrap_or(0);
+        let index_end = self
+            .index_end
+            .unwrap_or_else(|| lines[line_end - 1].chars().count())
+            .max(1);
+
+        let mut output = get_line_number_prefix(line_start, indent, config);
+        output.push_str("  ");
+        output.push_str(lines[line_start - 1]);
+        output.push('\n');
+
+        let mut start_marker = String::from(glyphs.span_start);
+        start_marker.extend(std::iter::repeat_n(glyphs.span_horizontal, index_start + 1));
+        start_marker.push(glyphs.span_pointer);
+        output.push_str(&get_blank_line_prefix(indent, config));
+        output.push_str(&config.paint(&start_marker, style));
+        output.push('\n');
+
+        let vertical = config.paint(&glyphs.span_vertical.to_string(), style);
+        for line_number in line_start + 1..=line_end {
+            output.push_str(&get_line_number_prefix(line_number, indent, config));
+            output.push_str(&vertical);
+            output.push(' ');
+            output.push_str(lines[line_number - 1]);
+            output.push('\n');
         }
 
+        let mut end_marker = String::from(glyphs.span_end);
+        end_marker.extend(std::iter::repeat_n(glyphs.span_horizontal, index_end));
+        end_marker.push(glyphs.span_pointer);
+        output.push_str(&get_blank_line_prefix(indent, config));
+        output.push_str(&config.paint(&end_marker, style));
+        output.push('\n');
         output
     }
 
-    pub(crate) fn build_caption(&self) -> String {
+    pub(crate) fn build_caption(&self, indent_size: usize, config: &RenderConfig) -> String {
         if let Some(caption) = &self.caption {
-            let mut output = " ".repeat(self.indent_size.unwrap() - 2);
-            output.push_str("--> ");
-            output.push_str(caption);
+            let theme = config.get_theme();
+            let mut output = " ".repeat(indent_size - 2);
+            output.push_str(&config.paint(
+                config.glyph_table().caption_arrow,
+                theme.get_highlight_kind(self.kind),
+            ));
+            output.push(' ');
+            output.push_str(&config.paint(caption, theme.get_caption()));
             output.push('\n');
             return output;
         }
         String::new()
     }
 
-    #[cfg(test)]
-    pub(crate) fn set_indent_size(mut self, indent_size: usize) -> Self {
-        self.indent_size = Ok(indent_size);
-        self
-    }
-
-    pub(crate) fn build(mut self) -> Result<String, FriendlyCodeSnippetError> {
-        self.calc_line_start_start_index();
-        self.calc_line_end_start_index();
-        self.validate_inputs()?;
-        self.calc_indent_size();
-        let mut output = String::new();
-        output.push_str(&self.build_file_url());
-        output.push_str(&self.build_caption());
-        output.push_str(&self.build_lines());
-        Ok(output)
+    /// Writes the snippet using the layout returned by
+    /// [`FriendlyCodeSnippet::validate_inputs`].
+    pub(crate) fn write_to(
+        &self,
+        layout: &SnippetLayout,
+        config: &RenderConfig,
+        output: &mut dyn fmt::Write,
+    ) -> fmt::Result {
+        let mut snippet = self.build_file_url(layout.indent_size, config);
+        snippet.push_str(&self.build_caption(layout.indent_size, config));
+        snippet.push_str(&self.build_lines(layout, config));
+        output.write_str(&snippet)
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{Color, ColorChoice, GlyphSet, Style, Theme};
     use indoc::indoc;
 
+    fn plain() -> RenderConfig {
+        RenderConfig::new().color(ColorChoice::Never)
+    }
+
     #[test]
     fn calc_line_start_start_index_test() {
         let code = "\nfn main() {\n    println!(\"Hello, world!\");\n}\n";
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(1);
-        friendly_code_snippet.calc_line_start_start_index();
-        assert_eq!(friendly_code_snippet.line_start_start_index, Ok(0));
+        assert_eq!(
+            FriendlyCodeSnippet::new(code)
+                .line_start(1)
+                .calc_line_start_start_index(),
+            Some(0)
+        );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(2);
-        friendly_code_snippet.calc_line_start_start_index();
-        assert_eq!(friendly_code_snippet.line_start_start_index, Ok(1));
+        assert_eq!(
+            FriendlyCodeSnippet::new(code)
+                .line_start(2)
+                .calc_line_start_start_index(),
+            Some(1)
+        );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(3);
-        friendly_code_snippet.calc_line_start_start_index();
-        assert_eq!(friendly_code_snippet.line_start_start_index, Ok(13));
+        assert_eq!(
+            FriendlyCodeSnippet::new(code)
+                .line_start(3)
+                .calc_line_start_start_index(),
+            Some(13)
+        );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(0);
-        friendly_code_snippet.calc_line_start_start_index();
         assert_eq!(
-            friendly_code_snippet.line_start_start_index,
-            Err(CalculatedFieldError::Invalid)
+            FriendlyCodeSnippet::new(code)
+                .line_start(0)
+                .calc_line_start_start_index(),
+            None
         );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(100);
-        friendly_code_snippet.calc_line_start_start_index();
         assert_eq!(
-            friendly_code_snippet.line_start_start_index,
-            Err(CalculatedFieldError::Invalid)
+            FriendlyCodeSnippet::new(code)
+                .line_start(100)
+                .calc_line_start_start_index(),
+            None
         );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code);
-        friendly_code_snippet.calc_line_start_start_index();
         assert_eq!(
-            friendly_code_snippet.line_start_start_index,
-            Err(CalculatedFieldError::Invalid)
+            FriendlyCodeSnippet::new(code).calc_line_start_start_index(),
+            None
         );
     }
 
@@ -336,30 +424,39 @@ mod test {
     fn calc_line_end_start_index_test() {
         let code = "\nfn main() {\n    println!(\"Hello, world!\");\n}\n";
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_end(1);
-        friendly_code_snippet.calc_line_end_start_index();
-        assert_eq!(friendly_code_snippet.line_end_start_index, Ok(0));
+        assert_eq!(
+            FriendlyCodeSnippet::new(code)
+                .line_end(1)
+                .calc_line_end_start_index(),
+            Some(0)
+        );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_end(2);
-        friendly_code_snippet.calc_line_end_start_index();
-        assert_eq!(friendly_code_snippet.line_end_start_index, Ok(1));
+        assert_eq!(
+            FriendlyCodeSnippet::new(code)
+                .line_end(2)
+                .calc_line_end_start_index(),
+            Some(1)
+        );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_end(3);
-        friendly_code_snippet.calc_line_end_start_index();
-        assert_eq!(friendly_code_snippet.line_end_start_index, Ok(13));
+        assert_eq!(
+            FriendlyCodeSnippet::new(code)
+                .line_end(3)
+                .calc_line_end_start_index(),
+            Some(13)
+        );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_end(0);
-        friendly_code_snippet.calc_line_end_start_index();
         assert_eq!(
-            friendly_code_snippet.line_end_start_index,
-            Err(CalculatedFieldError::Invalid)
+            FriendlyCodeSnippet::new(code)
+                .line_end(0)
+                .calc_line_end_start_index(),
+            None
         );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_end(100);
-        friendly_code_snippet.calc_line_end_start_index();
         assert_eq!(
-            friendly_code_snippet.line_end_start_index,
-            Err(CalculatedFieldError::Invalid)
+            FriendlyCodeSnippet::new(code)
+                .line_end(100)
+                .calc_line_end_start_index(),
+            None
         );
     }
 
@@ -368,101 +465,102 @@ mod test {
         let code = "\nfn main() {\n    println!(\"Hello, world!\");\n}\n";
 
         // everything is valid
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(1).line_end(2);
-        friendly_code_snippet.calc_line_start_start_index();
-        friendly_code_snippet.calc_line_end_start_index();
-        assert_eq!(friendly_code_snippet.validate_inputs(), Ok(true));
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(1).line_end(2);
+        assert_eq!(
+            friendly_code_snippet.validate_inputs(),
+            Ok(SnippetLayout {
+                line_start_start_index: 0,
+                line_end_start_index: 1,
+                indent_size: 4,
+            })
+        );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code)
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code)
             .line_start(1)
             .line_end(1)
             .index_start(3)
             .index_end(4);
-        friendly_code_snippet.calc_line_start_start_index();
-        friendly_code_snippet.calc_line_end_start_index();
-        assert_eq!(friendly_code_snippet.validate_inputs(), Ok(true));
+        assert!(friendly_code_snippet.validate_inputs().is_ok());
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code)
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code)
             .line_start(1)
             .line_end(2)
             .index_start(4)
             .index_end(4);
-        friendly_code_snippet.calc_line_start_start_index();
-        friendly_code_snippet.calc_line_end_start_index();
-        assert_eq!(friendly_code_snippet.validate_inputs(), Ok(true));
+        assert!(friendly_code_snippet.validate_inputs().is_ok());
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code)
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code)
             .line_start(1)
             .line_end(2)
             .index_start(3)
             .index_end(4);
-        friendly_code_snippet.calc_line_start_start_index();
-        friendly_code_snippet.calc_line_end_start_index();
-        assert_eq!(friendly_code_snippet.validate_inputs(), Ok(true));
+        assert!(friendly_code_snippet.validate_inputs().is_ok());
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(2);
-        friendly_code_snippet.calc_line_start_start_index();
-        friendly_code_snippet.calc_line_end_start_index();
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(2);
         assert_eq!(
             friendly_code_snippet.validate_inputs(),
             Err(FriendlyCodeSnippetError::MissingEndPosition)
         );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_end(2);
-        friendly_code_snippet.calc_line_start_start_index();
-        friendly_code_snippet.calc_line_end_start_index();
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code).line_end(2);
         assert_eq!(
             friendly_code_snippet.validate_inputs(),
             Err(FriendlyCodeSnippetError::MissingStartPosition)
         );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(0).line_end(2);
-        friendly_code_snippet.calc_line_start_start_index();
-        friendly_code_snippet.calc_line_end_start_index();
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(0).line_end(2);
         assert_eq!(
             friendly_code_snippet.validate_inputs(),
             Err(FriendlyCodeSnippetError::InvalidStartPosition)
         );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(1).line_end(100);
-        friendly_code_snippet.calc_line_start_start_index();
-        friendly_code_snippet.calc_line_end_start_index();
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(1).line_end(100);
         assert_eq!(
             friendly_code_snippet.validate_inputs(),
             Err(FriendlyCodeSnippetError::InvalidEndPosition)
         );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(2).line_end(1);
-        friendly_code_snippet.calc_line_start_start_index();
-        friendly_code_snippet.calc_line_end_start_index();
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(2).line_end(1);
         assert_eq!(
             friendly_code_snippet.validate_inputs(),
             Err(FriendlyCodeSnippetError::InvalidEndPosition)
         );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code)
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code)
             .line_start(2)
             .line_end(2)
             .index_start(4)
             .index_end(4);
-        friendly_code_snippet.calc_line_start_start_index();
-        friendly_code_snippet.calc_line_end_start_index();
         assert_eq!(
             friendly_code_snippet.validate_inputs(),
             Err(FriendlyCodeSnippetError::InvalidEndPosition)
         );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code)
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code)
             .line_start(2)
             .line_end(2)
             .index_start(4)
             .index_end(3);
-        friendly_code_snippet.calc_line_start_start_index();
-        friendly_code_snippet.calc_line_end_start_index();
         assert_eq!(
             friendly_code_snippet.validate_inputs(),
             Err(FriendlyCodeSnippetError::InvalidEndPosition)
         );
+
+        // a single line without columns
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code).line_start(2).line_end(2);
+        assert_eq!(
+            friendly_code_snippet.validate_inputs(),
+            Err(FriendlyCodeSnippetError::MissingStartPosition)
+        );
+
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code)
+            .line_start(2)
+            .line_end(2)
+            .index_start(4);
+        assert_eq!(
+            friendly_code_snippet.validate_inputs(),
+            Err(FriendlyCodeSnippetError::MissingEndPosition)
+        );
     }
 
     #[test]
@@ -481,58 +579,52 @@ mod test {
 
     #[test]
     fn get_line_number_prefix_test() {
-        assert_eq!(get_line_number_prefix(1, 4), "  1 | ");
-        assert_eq!(get_line_number_prefix(2, 4), "  2 | ");
-        assert_eq!(get_line_number_prefix(20, 4), " 20 | ");
-        assert_eq!(get_line_number_prefix(200, 4), "200 | ");
-        assert_eq!(get_line_number_prefix(200, 5), " 200 | ");
+        assert_eq!(get_line_number_prefix(1, 4, &plain()), "  1 | ");
+        assert_eq!(get_line_number_prefix(2, 4, &plain()), "  2 | ");
+        assert_eq!(get_line_number_prefix(20, 4, &plain()), " 20 | ");
+        assert_eq!(get_line_number_prefix(200, 4, &plain()), "200 | ");
+        assert_eq!(get_line_number_prefix(200, 5, &plain()), " 200 | ");
     }
 
     #[test]
     fn get_blank_line_prefix_test() {
-        assert_eq!(get_blank_line_prefix(4), "    | ");
-        assert_eq!(get_blank_line_prefix(5), "     | ");
+        assert_eq!(get_blank_line_prefix(4, &plain()), "    | ");
+        assert_eq!(get_blank_line_prefix(5, &plain()), "     | ");
         assert_eq!(
-            get_blank_line_prefix(7).len(),
-            get_line_number_prefix(1, 7).len()
+            get_blank_line_prefix(7, &plain()).len(),
+            get_line_number_prefix(1, 7, &plain()).len()
         );
     }
 
     #[test]
     fn build_file_url_test() {
         assert_eq!(
-            FriendlyCodeSnippet::new(String::new())
-                .set_indent_size(4)
-                .build_file_url(),
+            FriendlyCodeSnippet::new(String::new()).build_file_url(4, &plain()),
             ""
         );
         assert_eq!(
             FriendlyCodeSnippet::new(String::new())
                 .index_start(4)
-                .set_indent_size(4)
-                .build_file_url(),
+                .build_file_url(4, &plain()),
             "    4\n"
         );
         assert_eq!(
             FriendlyCodeSnippet::new(String::new())
                 .line_start(24)
                 .index_start(4)
-                .set_indent_size(4)
-                .build_file_url(),
+                .build_file_url(4, &plain()),
             "    24:4\n"
         );
         assert_eq!(
             FriendlyCodeSnippet::new(String::new())
                 .line_start(24)
-                .set_indent_size(4)
-                .build_file_url(),
+                .build_file_url(4, &plain()),
             "    24\n"
         );
         assert_eq!(
             FriendlyCodeSnippet::new(String::new())
                 .set_file_path("hello.rs")
-                .set_indent_size(4)
-                .build_file_url(),
+                .build_file_url(4, &plain()),
             "    hello.rs\n"
         );
         assert_eq!(
@@ -540,8 +632,7 @@ mod test {
                 .set_file_path("hello.rs")
                 .line_start(24)
                 .index_start(4)
-                .set_indent_size(4)
-                .build_file_url(),
+                .build_file_url(4, &plain()),
             "    hello.rs:24:4\n"
         );
         assert_eq!(
@@ -549,8 +640,7 @@ mod test {
                 .set_file_path("hello.rs")
                 .line_start(24)
                 .index_start(4)
-                .set_indent_size(8)
-                .build_file_url(),
+                .build_file_url(8, &plain()),
             "        hello.rs:24:4\n"
         );
     }
@@ -565,33 +655,27 @@ mod test {
             "
         };
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code)
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code)
             .set_file_path("hello.rs")
             .line_start(1)
             .index_start(3)
             .line_end(1)
             .index_end(7);
-        friendly_code_snippet.calc_line_start_start_index();
-        friendly_code_snippet.calc_line_end_start_index();
-        friendly_code_snippet.validate_inputs().unwrap();
-        friendly_code_snippet.calc_indent_size();
+        let layout = friendly_code_snippet.validate_inputs().unwrap();
         assert_eq!(
-            friendly_code_snippet.build_lines(),
+            friendly_code_snippet.build_lines(&layout, &plain()),
             "  1 | fn main() {\n    |    ^^^^\n"
         );
 
-        let mut friendly_code_snippet = FriendlyCodeSnippet::new(code)
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code)
             .set_file_path("hello.rs")
             .line_start(2)
             .index_start(4)
             .line_end(2)
             .index_end(11);
-        friendly_code_snippet.calc_line_start_start_index();
-        friendly_code_snippet.calc_line_end_start_index();
-        friendly_code_snippet.validate_inputs().unwrap();
-        friendly_code_snippet.calc_indent_size();
+        let layout = friendly_code_snippet.validate_inputs().unwrap();
         assert_eq!(
-            friendly_code_snippet.build_lines(),
+            friendly_code_snippet.build_lines(&layout, &plain()),
             "  2 |     println!(\"Hello, world!\");\n    |     ^^^^^^^\n"
         );
     }
@@ -599,24 +683,85 @@ mod test {
     #[test]
     fn build_caption_test() {
         assert_eq!(
-            FriendlyCodeSnippet::new(String::new())
-                .set_indent_size(4)
-                .build_caption(),
+            FriendlyCodeSnippet::new(String::new()).build_caption(4, &plain()),
             ""
         );
         assert_eq!(
             FriendlyCodeSnippet::new(String::new())
                 .caption("hello world")
-                .set_indent_size(4)
-                .build_caption(),
+                .build_caption(4, &plain()),
             "  --> hello world\n"
         );
         assert_eq!(
             FriendlyCodeSnippet::new(String::new())
                 .caption("hello world")
-                .set_indent_size(8)
-                .build_caption(),
+                .build_caption(8, &plain()),
             "      --> hello world\n"
         );
     }
+
+    #[test]
+    fn themed_snippet_test() {
+        let style = Style::new().color(Color::Green);
+        let config = RenderConfig::new()
+            .color(ColorChoice::Always)
+            .theme(Theme::monochrome().gutter(style).line_number(style));
+        assert_eq!(
+            get_line_number_prefix(2, 4, &config),
+            "  \x1b[32m2\x1b[0m \x1b[32m|\x1b[0m "
+        );
+        let friendly_code_snippet = FriendlyCodeSnippet::new("let x = foo;")
+            .line_start(1)
+            .index_start(8)
+            .line_end(1)
+            .index_end(11)
+            .kind(HighlightKind::Warning)
+            .caption("foo is not defined");
+        let layout = friendly_code_snippet.validate_inputs().unwrap();
+        assert!(friendly_code_snippet
+            .build_lines(&layout, &config)
+            .ends_with("        \x1b[1m^^^\x1b[0m\n"));
+        assert_eq!(
+            friendly_code_snippet.build_caption(layout.indent_size, &config),
+            "  \x1b[1m-->\x1b[0m \x1b[4mfoo is not defined\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn build_multiline_test() {
+        let code = indoc! {
+            "
+            fn main() {
+                println!(\"Hello, world!\");
+            }
+            "
+        };
+
+        let friendly_code_snippet = FriendlyCodeSnippet::new(code)
+            .line_start(1)
+            .index_start(10)
+            .line_end(3)
+            .index_end(1);
+        let layout = friendly_code_snippet.validate_inputs().unwrap();
+        assert_eq!(
+            friendly_code_snippet.build_lines(&layout, &plain()),
+            concat!(
+                "  1 |   fn main() {\n",
+                "    |  ___________^\n",
+                "  2 | |     println!(\"Hello, world!\");\n",
+                "  3 | | }\n",
+                "    | |_^\n",
+            )
+        );
+        assert_eq!(
+            friendly_code_snippet.build_lines(&layout, &plain().glyphs(GlyphSet::Unicode)),
+            concat!(
+                "  1 │   fn main() {\n",
+                "    │ ╭───────────┘\n",
+                "  2 │ │     println!(\"Hello, world!\");\n",
+                "  3 │ │ }\n",
+                "    │ ╰─┘\n",
+            )
+        );
+    }
 }
@@ -0,0 +1,201 @@
+use super::FriendlyCodeSnippet;
+
+/// Where a snippet's highlighted span sits in its file. Lines are 1-based,
+/// columns are 0-based character offsets within their line, and byte offsets
+/// are 0-based from the start of the file. Ends are exclusive.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct SpanPosition {
+    pub line_start: usize,
+    pub column_start: usize,
+    pub line_end: usize,
+    pub column_end: usize,
+    pub byte_start: usize,
+    pub byte_end: usize,
+}
+
+fn get_byte_offset(contents: &str, line: usize, column: usize) -> Option<usize> {
+    let mut line_offset = 0;
+    for (index, line_contents) in contents.split('\n').enumerate() {
+        if index + 1 == line {
+            let column_offset = line_contents
+                .char_indices()
+                .map(|(offset, _)| offset)
+                .chain(std::iter::once(line_contents.len()))
+                .nth(column)?;
+            return Some(line_offset + column_offset);
+        }
+        line_offset += line_contents.len() + 1;
+    }
+    None
+}
+
+impl FriendlyCodeSnippet {
+    /// Returns `None` if the span doesn't fit inside the file contents or
+    /// ends before it starts. A missing start column means the start of the
+    /// line, and a missing end column the end of the line.
+    pub fn get_position(&self) -> Option<SpanPosition> {
+        let line_start = self.line_start?;
+        let line_end = self.line_end.unwrap_or(line_start);
+        let column_start = self.index_start.unwrap_or(0);
+        let column_end = match self.index_end {
+            Some(index_end) => index_end,
+            None => self
+                .file_contents
+                .split('\n')
+                .nth(line_end.checked_sub(1)?)?
+                .chars()
+                .count(),
+        };
+        if (line_end, column_end) < (line_start, column_start) {
+            return None;
+        }
+        Some(SpanPosition {
+            line_start,
+            column_start,
+            line_end,
+            column_end,
+            byte_start: get_byte_offset(&self.file_contents, line_start, column_start)?,
+            byte_end: get_byte_offset(&self.file_contents, line_end, column_end)?,
+        })
+    }
+
+    /// `path:line:column`, or `line:column` without a file path. The column
+    /// is 1-based, as editors expect.
+    pub(crate) fn format_location(&self, position: &SpanPosition) -> String {
+        let line_column = format!("{}:{}", position.line_start, position.column_start + 1);
+        match self.get_file_path() {
+            Some(file_path) => format!("{}:{}", file_path, line_column),
+            None => line_column,
+        }
+    }
+
+    /// The text of every line the span touches, without line endings.
+    pub fn get_span_lines(&self) -> Vec<&str> {
+        match self.get_position() {
+            Some(position) => self
+                .file_contents
+                .split('\n')
+                .skip(position.line_start - 1)
+                .take(position.line_end + 1 - position.line_start)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::FriendlyCodeSnippetError;
+
+    #[test]
+    fn get_byte_offset_test() {
+        let code = "fn main() {\n    let café = 1;\n}";
+        assert_eq!(get_byte_offset(code, 1, 0), Some(0));
+        assert_eq!(get_byte_offset(code, 1, 11), Some(11));
+        assert_eq!(get_byte_offset(code, 2, 4), Some(16));
+        assert_eq!(get_byte_offset(code, 2, 13), Some(26));
+        assert_eq!(get_byte_offset(code, 3, 1), Some(32));
+        assert_eq!(get_byte_offset(code, 2, 20), None);
+        assert_eq!(get_byte_offset(code, 4, 0), None);
+    }
+
+    #[test]
+    fn get_position_test() {
+        let code = "fn main() {\n    let x = foo;\n}";
+        assert_eq!(
+            FriendlyCodeSnippet::new(code)
+                .line_start(2)
+                .index_start(12)
+                .line_end(2)
+                .index_end(15)
+                .get_position(),
+            Some(SpanPosition {
+                line_start: 2,
+                column_start: 12,
+                line_end: 2,
+                column_end: 15,
+                byte_start: 24,
+                byte_end: 27,
+            })
+        );
+        assert_eq!(
+            FriendlyCodeSnippet::new(code)
+                .line_start(1)
+                .line_end(2)
+                .get_position(),
+            Some(SpanPosition {
+                line_start: 1,
+                column_start: 0,
+                line_end: 2,
+                column_end: 16,
+                byte_start: 0,
+                byte_end: 28,
+            })
+        );
+        assert_eq!(FriendlyCodeSnippet::new(code).get_position(), None);
+        assert_eq!(
+            FriendlyCodeSnippet::new(code)
+                .line_start(5)
+                .line_end(5)
+                .get_position(),
+            None
+        );
+    }
+
+    #[test]
+    fn format_location_test() {
+        let snippet = FriendlyCodeSnippet::new("let x = foo;")
+            .line_start(1)
+            .index_start(8)
+            .line_end(1)
+            .index_end(11);
+        let position = snippet.get_position().unwrap();
+        assert_eq!(snippet.format_location(&position), "1:9");
+        assert_eq!(
+            snippet
+                .set_file_path("src/main.rs")
+                .format_location(&position),
+            "src/main.rs:1:9"
+        );
+    }
+
+    #[test]
+    fn reversed_position_test() {
+        let code = "fn main() {\n    let x = foo;\n}";
+        assert_eq!(
+            FriendlyCodeSnippet::new(code)
+                .line_start(2)
+                .line_end(1)
+                .get_position(),
+            None
+        );
+        assert_eq!(
+            FriendlyCodeSnippet::new(code)
+                .line_start(2)
+                .index_start(12)
+                .line_end(2)
+                .index_end(8)
+                .get_position(),
+            None
+        );
+        let reversed = FriendlyCodeSnippet::new(code).line_start(3).line_end(2);
+        assert!(reversed.get_span_lines().is_empty());
+        assert_eq!(
+            reversed.validate_inputs(),
+            Err(FriendlyCodeSnippetError::InvalidEndPosition)
+        );
+    }
+
+    #[test]
+    fn get_span_lines_test() {
+        let code = "fn main() {\n    let x = foo;\n}";
+        assert_eq!(
+            FriendlyCodeSnippet::new(code)
+                .line_start(2)
+                .line_end(3)
+                .get_span_lines(),
+            vec!["    let x = foo;", "}"]
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::env;
+use std::ffi::OsString;
+
+/// Whether a render should include ANSI colors.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ColorChoice {
+    Always,
+    Never,
+    /// Color only when writing to a terminal. `NO_COLOR` turns colors off and
+    /// `CLICOLOR_FORCE` turns them on regardless of the target, with
+    /// `NO_COLOR` taking precedence.
+    Auto,
+}
+
+impl ColorChoice {
+    pub fn should_colorize(self, is_terminal: bool) -> bool {
+        self.resolve(
+            is_terminal,
+            env::var_os("NO_COLOR"),
+            env::var_os("CLICOLOR_FORCE"),
+        )
+    }
+
+    fn resolve(
+        self,
+        is_terminal: bool,
+        no_color: Option<OsString>,
+        clicolor_force: Option<OsString>,
+    ) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                if no_color.is_some_and(|value| !value.is_empty()) {
+                    return false;
+                }
+                if clicolor_force.is_some_and(|value| !value.is_empty() && value != "0") {
+                    return true;
+                }
+                is_terminal
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn resolve_test() {
+        let set = |value: &str| Some(OsString::from(value));
+
+        assert!(ColorChoice::Always.resolve(false, set("1"), None));
+        assert!(!ColorChoice::Never.resolve(true, None, set("1")));
+
+        assert!(ColorChoice::Auto.resolve(true, None, None));
+        assert!(!ColorChoice::Auto.resolve(false, None, None));
+        assert!(!ColorChoice::Auto.resolve(true, set("1"), None));
+        assert!(ColorChoice::Auto.resolve(true, set(""), None));
+        assert!(ColorChoice::Auto.resolve(false, None, set("1")));
+        assert!(!ColorChoice::Auto.resolve(false, None, set("0")));
+        assert!(!ColorChoice::Auto.resolve(false, set("1"), set("1")));
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{FriendlyError, FriendlyErrorError};
+
+/// Which parts of a diagnostic decide whether two diagnostics are duplicates
+/// of each other. By default that's the kind, error code, title and primary
+/// span, i.e. where the first code snippet points.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct DedupKey {
+    code: bool,
+    kind: bool,
+    primary_span: bool,
+    summary: bool,
+    title: bool,
+}
+
+impl DedupKey {
+    pub fn new() -> Self {
+        DedupKey {
+            code: true,
+            kind: true,
+            primary_span: true,
+            summary: false,
+            title: true,
+        }
+    }
+
+    pub fn code(mut self, code: bool) -> Self {
+        self.code = code;
+        self
+    }
+
+    pub fn kind(mut self, kind: bool) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn primary_span(mut self, primary_span: bool) -> Self {
+        self.primary_span = primary_span;
+        self
+    }
+
+    pub fn summary(mut self, summary: bool) -> Self {
+        self.summary = summary;
+        self
+    }
+
+    pub fn title(mut self, title: bool) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn get_code(&self) -> bool {
+        self.code
+    }
+
+    pub fn get_kind(&self) -> bool {
+        self.kind
+    }
+
+    pub fn get_primary_span(&self) -> bool {
+        self.primary_span
+    }
+
+    pub fn get_summary(&self) -> bool {
+        self.summary
+    }
+
+    pub fn get_title(&self) -> bool {
+        self.title
+    }
+
+    fn get_span(error: &FriendlyError) -> Result<Option<String>, FriendlyErrorError> {
+        let snippet = match error.get_code_snippets().first() {
+            Some(snippet) => snippet,
+            None => return Ok(None),
+        };
+        snippet
+            .validate_inputs()
+            .map_err(FriendlyErrorError::CodeSnippetError)?;
+        Ok(Some(format!(
+            "{}:{:?}",
+            snippet.get_file_path().unwrap_or_default(),
+            snippet.get_position()
+        )))
+    }
+
+    /// The parts of `error` this key compares. Diagnostics are duplicates if
+    /// their keys are equal.
+    pub(crate) fn key_for(
+        &self,
+        error: &FriendlyError,
+    ) -> Result<Vec<Option<String>>, FriendlyErrorError> {
+        let mut key = Vec::new();
+        if self.kind {
+            key.push(Some(format!("{:?}", error.get_kind())));
+        }
+        if self.code {
+            key.push(error.get_error_code().map(String::from));
+        }
+        if self.title {
+            key.push(error.get_title().map(String::from));
+        }
+        if self.summary {
+            key.push(error.get_summary().map(String::from));
+        }
+        if self.primary_span {
+            key.push(Self::get_span(error)?);
+        }
+        Ok(key)
+    }
+}
+
+impl Default for DedupKey {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ErrorKind, FriendlyCodeSnippet};
+
+    fn snippet(index_start: usize) -> FriendlyCodeSnippet {
+        FriendlyCodeSnippet::new("let x = foo;")
+            .set_file_path("src/main.rs")
+            .line_start(1)
+            .index_start(index_start)
+            .line_end(1)
+            .index_end(11)
+    }
+
+    #[test]
+    fn default_key_test() {
+        let key = DedupKey::new();
+        let error = FriendlyError::new()
+            .error_code("E1234")
+            .title("variable is undefined")
+            .add_code_snippet(snippet(8));
+        assert_eq!(
+            key.key_for(&error),
+            key.key_for(&error.clone().summary("foo is never defined"))
+        );
+        assert_ne!(
+            key.key_for(&error),
+            key.key_for(&error.clone().kind(ErrorKind::Warning))
+        );
+        assert_ne!(
+            key.key_for(&error),
+            key.key_for(
+                &FriendlyError::new()
+                    .error_code("E1234")
+                    .title("variable is undefined")
+                    .add_code_snippet(snippet(4))
+            )
+        );
+    }
+
+    #[test]
+    fn custom_key_test() {
+        let key = DedupKey::new().primary_span(false).summary(true);
+        let error = FriendlyError::new()
+            .title("variable is undefined")
+            .add_code_snippet(snippet(8));
+        assert_eq!(
+            key.key_for(&error),
+            key.key_for(&error.clone().add_code_snippet(snippet(4)))
+        );
+        assert_ne!(
+            key.key_for(&error),
+            key.key_for(&error.clone().summary("foo is never defined"))
+        );
+    }
+}
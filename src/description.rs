@@ -1,12 +1,19 @@
+use crate::output::Output;
 use crate::FriendlyError;
+use std::fmt::{self, Write};
 
 impl FriendlyError {
+    #[deprecated(note = "use `FriendlyError::render` or a `Renderer` instead")]
     pub fn print_description(&mut self) {
-        if let Some(url) = &self.data.description {
-            let url = url.clone();
-            self.add_empty_line();
-            self.output.push_str(&url);
+        let _ = self.print_with(|error, output| error.write_description(output));
+    }
+
+    pub(crate) fn write_description(&self, output: &mut Output) -> fmt::Result {
+        if let Some(description) = self.get_description() {
+            output.add_empty_line()?;
+            output.write_str(description)?;
         }
+        Ok(())
     }
 }
 
@@ -17,13 +24,35 @@ mod test {
 
     #[test]
     fn test_append_description() {
-        let mut error = FriendlyError::new().description("I am the description");
-        error.print_description();
-        assert_eq!(error.output, "I am the description");
+        let error = FriendlyError::new().description("I am the description");
+        let mut output = String::new();
+        error
+            .write_description(&mut Output::new(&mut output))
+            .unwrap();
+        assert_eq!(output, "I am the description");
     }
 
     #[test]
     fn test_append_description_with_output() {
+        let error = FriendlyError::new().description("I am the description");
+        let mut output = String::new();
+        let mut writer = Output::new(&mut output);
+        writer.write_str("Error message").unwrap();
+        error.write_description(&mut writer).unwrap();
+        assert_eq!(
+            output,
+            indoc!(
+                "
+                Error message
+
+                I am the description"
+            )
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_print_description() {
         let mut error = FriendlyError::new()
             .description("I am the description")
             .set_output("Error message");
@@ -1,13 +1,37 @@
-use crate::FriendlyError;
+use crate::output::Output;
+use crate::{FriendlyError, RenderConfig};
+use std::fmt::{self, Write};
 
 impl FriendlyError {
+    /// The explicit `doc_url`, else the config's doc URL template filled in
+    /// with the error code.
+    pub(crate) fn resolve_doc_url(&self, config: &RenderConfig) -> Option<String> {
+        if let Some(url) = self.get_doc_url() {
+            return Some(url.to_string());
+        }
+        config.format_doc_url(self.get_error_code()?)
+    }
+
+    #[deprecated(note = "use `FriendlyError::render` or a `Renderer` instead")]
     pub fn print_doc_url(&mut self) {
-        if let Some(url) = &self.data.doc_url {
-            let url = url.clone();
-            self.add_empty_line();
-            self.output.push_str("To learn more, read the docs at ");
-            self.output.push_str(&url);
+        let config = RenderConfig::default().resolved();
+        let _ = self.print_with(|error, output| error.write_doc_url(&config, output));
+    }
+
+    pub(crate) fn write_doc_url(&self, config: &RenderConfig, output: &mut Output) -> fmt::Result {
+        if let Some(url) = self.resolve_doc_url(config) {
+            output.add_empty_line()?;
+            output.write_str(&config.get_catalog().format_docs_link(&url))?;
         }
+        Ok(())
+    }
+}
+
+impl RenderConfig {
+    /// The doc URL template filled in with `code`.
+    pub(crate) fn format_doc_url(&self, code: &str) -> Option<String> {
+        let template = self.get_doc_url_template()?;
+        Some(template.replace("{code}", code))
     }
 }
 
@@ -18,16 +42,40 @@ mod test {
 
     #[test]
     fn test_append_doc_url() {
-        let mut error = FriendlyError::new().doc_url("https://example.com/");
-        error.print_doc_url();
+        let error = FriendlyError::new().doc_url("https://example.com/");
+        let mut output = String::new();
+        error
+            .write_doc_url(&RenderConfig::default(), &mut Output::new(&mut output))
+            .unwrap();
         assert_eq!(
-            error.output,
+            output,
             "To learn more, read the docs at https://example.com/"
         );
     }
 
     #[test]
     fn test_append_doc_url_with_output() {
+        let error = FriendlyError::new().doc_url("https://example.com/");
+        let mut output = String::new();
+        let mut writer = Output::new(&mut output);
+        writer.write_str("Error message").unwrap();
+        error
+            .write_doc_url(&RenderConfig::default(), &mut writer)
+            .unwrap();
+        assert_eq!(
+            output,
+            indoc!(
+                "
+                Error message
+
+                To learn more, read the docs at https://example.com/"
+            )
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_print_doc_url() {
         let mut error = FriendlyError::new()
             .doc_url("https://example.com/")
             .set_output("Error message");
@@ -42,4 +90,37 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn test_doc_url_template() {
+        let config = RenderConfig::new().doc_url_template("https://docs.example.com/errors/{code}");
+        let mut output = String::new();
+        FriendlyError::new()
+            .error_code("E1234")
+            .write_doc_url(&config, &mut Output::new(&mut output))
+            .unwrap();
+        assert_eq!(
+            output,
+            "To learn more, read the docs at https://docs.example.com/errors/E1234"
+        );
+    }
+
+    #[test]
+    fn test_doc_url_template_fallback() {
+        let config = RenderConfig::new().doc_url_template("https://docs.example.com/errors/{code}");
+        let error = FriendlyError::new()
+            .error_code("E1234")
+            .doc_url("https://example.com/");
+        assert_eq!(
+            error.resolve_doc_url(&config),
+            Some("https://example.com/".to_string())
+        );
+        assert_eq!(FriendlyError::new().resolve_doc_url(&config), None);
+        assert_eq!(
+            FriendlyError::new()
+                .error_code("E1234")
+                .resolve_doc_url(&RenderConfig::default()),
+            None
+        );
+    }
 }
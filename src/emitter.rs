@@ -0,0 +1,981 @@
+use crate::{
+    DedupKey, ErrorKind, FriendlyError, FriendlyErrorError, FriendlyRenderer, LintLevels, Message,
+    MessageCatalog, RenderConfig, Renderer,
+};
+use std::collections::HashMap;
+use std::io;
+
+/// How many diagnostics of each [`ErrorKind`] a [`DiagnosticEmitter`] has seen.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct DiagnosticCounts {
+    errors: usize,
+    warnings: usize,
+    improvements: usize,
+    code_style: usize,
+}
+
+impl DiagnosticCounts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, kind: ErrorKind) -> usize {
+        match kind {
+            ErrorKind::Error => self.errors,
+            ErrorKind::Warning => self.warnings,
+            ErrorKind::Improvement => self.improvements,
+            ErrorKind::CodeStyle => self.code_style,
+        }
+    }
+
+    pub fn get_total(&self) -> usize {
+        self.errors + self.warnings + self.improvements + self.code_style
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors > 0
+    }
+
+    /// `1` if any [`ErrorKind::Error`] was emitted, otherwise `0`.
+    pub fn exit_code(&self) -> i32 {
+        if self.has_errors() {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// A sentence such as `3 errors, 2 warnings emitted` in the language of
+    /// `catalog`, or `None` if nothing was emitted.
+    pub fn summary(&self, catalog: &MessageCatalog) -> Option<String> {
+        let parts: Vec<String> = [
+            ErrorKind::Error,
+            ErrorKind::Warning,
+            ErrorKind::Improvement,
+            ErrorKind::CodeStyle,
+        ]
+        .into_iter()
+        .filter(|kind| self.get(*kind) > 0)
+        .map(|kind| {
+            let count = self.get(kind);
+            catalog.format(
+                Message::count(kind, count),
+                &[("count", &count.to_string())],
+            )
+        })
+        .collect();
+        if parts.is_empty() {
+            return None;
+        }
+        Some(catalog.format(Message::SummaryFooter, &[("counts", &parts.join(", "))]))
+    }
+
+    pub(crate) fn add(&mut self, kind: ErrorKind) {
+        match kind {
+            ErrorKind::Error => self.errors += 1,
+            ErrorKind::Warning => self.warnings += 1,
+            ErrorKind::Improvement => self.improvements += 1,
+            ErrorKind::CodeStyle => self.code_style += 1,
+        }
+    }
+}
+
+/// Orders diagnostics by file, position, kind and code. Diagnostics without
+/// a file go last.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
+struct SortKey {
+    missing_file: bool,
+    file: String,
+    line: usize,
+    column: usize,
+    kind: usize,
+    code: Option<String>,
+}
+
+impl SortKey {
+    fn new(error: &FriendlyError) -> Result<Self, FriendlyErrorError> {
+        let snippet = error.get_code_snippets().first();
+        if let Some(snippet) = snippet {
+            snippet
+                .validate_inputs()
+                .map_err(FriendlyErrorError::CodeSnippetError)?;
+        }
+        let file = snippet.and_then(|snippet| snippet.get_file_path());
+        let position = snippet.and_then(|snippet| snippet.get_position());
+        Ok(SortKey {
+            missing_file: file.is_none(),
+            file: file.unwrap_or_default().to_string(),
+            line: position.map_or(0, |position| position.line_start),
+            column: position.map_or(0, |position| position.column_start),
+            kind: match error.get_kind() {
+                ErrorKind::Error => 0,
+                ErrorKind::Warning => 1,
+                ErrorKind::Improvement => 2,
+                ErrorKind::CodeStyle => 3,
+            },
+            code: error.get_error_code().map(String::from),
+        })
+    }
+}
+
+/// Builds the footer line from the final counts.
+type FooterFormat = dyn Fn(&DiagnosticCounts) -> Option<String>;
+
+/// A rendered diagnostic that hasn't been written yet.
+struct Pending {
+    kind: ErrorKind,
+    occurrences: usize,
+    rendered: String,
+    sort_key: SortKey,
+}
+
+/// Renders diagnostics into a writer as they arrive and keeps count of them.
+///
+/// Call [`DiagnosticEmitter::finish`] once every diagnostic has been emitted
+/// to print the summary footer.
+pub struct DiagnosticEmitter<W: io::Write> {
+    config: RenderConfig,
+    counts: DiagnosticCounts,
+    deduplicate: Option<DedupKey>,
+    errors_written: usize,
+    footer: bool,
+    footer_format: Option<Box<FooterFormat>>,
+    lint_levels: Option<LintLevels>,
+    max_errors: Option<usize>,
+    pending: Vec<Pending>,
+    renderer: Box<dyn Renderer>,
+    report_duplicates: bool,
+    seen: HashMap<Vec<Option<String>>, usize>,
+    separator: String,
+    sort: bool,
+    suppressed: usize,
+    writer: W,
+    written: usize,
+}
+
+impl<W: io::Write> DiagnosticEmitter<W> {
+    pub fn new(writer: W) -> Self {
+        DiagnosticEmitter {
+            config: RenderConfig::default(),
+            counts: DiagnosticCounts::new(),
+            deduplicate: None,
+            errors_written: 0,
+            footer: true,
+            footer_format: None,
+            lint_levels: None,
+            max_errors: None,
+            pending: Vec::new(),
+            renderer: Box::new(FriendlyRenderer),
+            report_duplicates: false,
+            seen: HashMap::new(),
+            separator: "\n".to_string(),
+            sort: false,
+            suppressed: 0,
+            writer,
+            written: 0,
+        }
+    }
+
+    pub fn config(mut self, config: RenderConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Drops diagnostics that match an earlier one under `key`. Duplicates
+    /// aren't counted.
+    pub fn deduplicate(mut self, key: DedupKey) -> Self {
+        self.deduplicate = Some(key);
+        self
+    }
+
+    /// Whether [`DiagnosticEmitter::finish`] prints a line such as
+    /// `3 errors, 2 warnings emitted`. Defaults to `true`.
+    pub fn footer(mut self, footer: bool) -> Self {
+        self.footer = footer;
+        self
+    }
+
+    /// Replaces the default footer wording. `format` gets the final counts
+    /// and returns the footer line, or `None` to leave it out.
+    pub fn footer_format<F>(mut self, format: F) -> Self
+    where
+        F: Fn(&DiagnosticCounts) -> Option<String> + 'static,
+    {
+        self.footer_format = Some(Box::new(format));
+        self
+    }
+
+    /// Applies `lint_levels` to every diagnostic before it's rendered, which
+    /// can change its kind or drop it entirely. Dropped diagnostics aren't
+    /// counted.
+    pub fn lint_levels(mut self, lint_levels: LintLevels) -> Self {
+        self.lint_levels = Some(lint_levels);
+        self
+    }
+
+    /// Stops writing diagnostics once `max_errors` diagnostics of kind
+    /// [`ErrorKind::Error`] have been written, and ends the output with
+    /// `aborting after 10 errors; 42 more not shown`. Diagnostics that aren't
+    /// shown are still counted. `0` means no limit.
+    pub fn max_errors(mut self, max_errors: usize) -> Self {
+        self.max_errors = Some(max_errors).filter(|max_errors| *max_errors > 0);
+        self
+    }
+
+    pub fn renderer<R: Renderer + 'static>(mut self, renderer: R) -> Self {
+        self.renderer = Box::new(renderer);
+        self
+    }
+
+    /// Adds a `(reported 3 times)` line after diagnostics that were
+    /// deduplicated. Since the final count is only known at the end, this
+    /// holds every diagnostic back until [`DiagnosticEmitter::finish`].
+    pub fn report_duplicates(mut self, report_duplicates: bool) -> Self {
+        self.report_duplicates = report_duplicates;
+        self
+    }
+
+    /// Written between diagnostics and before the footer. Defaults to a blank
+    /// line; use an empty string for one-line renderers such as
+    /// [`ShortRenderer`](crate::ShortRenderer).
+    pub fn separator<S: Into<String>>(mut self, separator: S) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Holds every diagnostic back until [`DiagnosticEmitter::finish`], then
+    /// writes them sorted by file path, line, column, kind and error code, so
+    /// output doesn't depend on the order diagnostics arrived in. Diagnostics
+    /// that compare equal keep their original order.
+    pub fn sort(mut self, sort: bool) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    pub fn get_config(&self) -> &RenderConfig {
+        &self.config
+    }
+
+    pub fn get_counts(&self) -> DiagnosticCounts {
+        self.counts
+    }
+
+    pub fn get_deduplicate(&self) -> Option<DedupKey> {
+        self.deduplicate
+    }
+
+    pub fn get_footer(&self) -> bool {
+        self.footer
+    }
+
+    pub fn get_lint_levels(&self) -> Option<&LintLevels> {
+        self.lint_levels.as_ref()
+    }
+
+    pub fn get_max_errors(&self) -> Option<usize> {
+        self.max_errors
+    }
+
+    /// How many diagnostics were held back by
+    /// [`DiagnosticEmitter::max_errors`] so far.
+    pub fn get_suppressed(&self) -> usize {
+        self.suppressed
+    }
+
+    pub fn get_report_duplicates(&self) -> bool {
+        self.report_duplicates
+    }
+
+    pub fn get_separator(&self) -> &str {
+        &self.separator
+    }
+
+    pub fn get_sort(&self) -> bool {
+        self.sort
+    }
+
+    /// See [`DiagnosticCounts::exit_code`].
+    pub fn exit_code(&self) -> i32 {
+        self.counts.exit_code()
+    }
+
+    fn write_str(&mut self, string: &str) -> Result<(), FriendlyErrorError> {
+        self.writer
+            .write_all(string.as_bytes())
+            .map_err(|err| FriendlyErrorError::IoError(err.kind()))
+    }
+
+    fn is_buffered(&self) -> bool {
+        self.sort || (self.deduplicate.is_some() && self.report_duplicates)
+    }
+
+    fn write_separator(&mut self) -> Result<(), FriendlyErrorError> {
+        if self.written > 0 {
+            let separator = self.separator.clone();
+            self.write_str(&separator)?;
+        }
+        Ok(())
+    }
+
+    fn is_cut_off(&self) -> bool {
+        self.max_errors
+            .is_some_and(|max_errors| self.errors_written >= max_errors)
+    }
+
+    fn write_diagnostic(&mut self, pending: &Pending) -> Result<(), FriendlyErrorError> {
+        if self.is_cut_off() {
+            self.suppressed += 1;
+            return Ok(());
+        }
+        self.write_separator()?;
+        self.write_str(&pending.rendered)?;
+        if !pending.rendered.ends_with('\n') {
+            self.write_str("\n")?;
+        }
+        if self.report_duplicates && pending.occurrences > 1 {
+            let reported = self.config.get_catalog().format(
+                Message::ReportedTimes,
+                &[("count", &pending.occurrences.to_string())],
+            );
+            self.write_str(&reported)?;
+            self.write_str("\n")?;
+        }
+        self.written += 1;
+        if pending.kind == ErrorKind::Error {
+            self.errors_written += 1;
+        }
+        Ok(())
+    }
+
+    /// Renders `error` and writes it out, unless it's a duplicate. Nothing is
+    /// written or counted if the error can't be rendered. Once the
+    /// [`DiagnosticEmitter::max_errors`] cutoff is reached, diagnostics that
+    /// aren't held back are only validated and counted, without being
+    /// rendered.
+    pub fn emit(&mut self, error: FriendlyError) -> Result<(), FriendlyErrorError> {
+        let error = match &self.lint_levels {
+            Some(lint_levels) => match lint_levels.apply(error, self.config.get_catalog()) {
+                Some(error) => error,
+                None => return Ok(()),
+            },
+            None => error,
+        };
+        let sort_key = if self.sort {
+            SortKey::new(&error)?
+        } else {
+            SortKey::default()
+        };
+        let key = match self.deduplicate {
+            Some(key) => Some(key.key_for(&error)?),
+            None => None,
+        };
+        if let Some(key) = &key {
+            if let Some(&index) = self.seen.get(key) {
+                if let Some(pending) = self.pending.get_mut(index) {
+                    pending.occurrences += 1;
+                }
+                return Ok(());
+            }
+        }
+        let rendered = if !self.is_buffered() && self.is_cut_off() {
+            error.validate(&self.config)?;
+            None
+        } else {
+            Some(error.render_with(self.renderer.as_ref(), &self.config)?)
+        };
+        if let Some(key) = key {
+            self.seen.insert(key, self.pending.len());
+        }
+        self.counts.add(error.get_kind());
+        let rendered = match rendered {
+            Some(rendered) => rendered,
+            None => {
+                self.suppressed += 1;
+                return Ok(());
+            }
+        };
+        let pending = Pending {
+            kind: error.get_kind(),
+            occurrences: 1,
+            rendered,
+            sort_key,
+        };
+        if self.is_buffered() {
+            self.pending.push(pending);
+            return Ok(());
+        }
+        self.write_diagnostic(&pending)
+    }
+
+    /// Prints the summary footer, flushes the writer, and returns the counts.
+    pub fn finish(mut self) -> Result<DiagnosticCounts, FriendlyErrorError> {
+        let mut pending = std::mem::take(&mut self.pending);
+        if self.sort {
+            pending.sort_by(|a, b| a.sort_key.cmp(&b.sort_key));
+        }
+        for pending in pending {
+            self.write_diagnostic(&pending)?;
+        }
+        if self.suppressed > 0 {
+            self.write_separator()?;
+            let message = if self.errors_written == 1 {
+                Message::AbortingOne
+            } else {
+                Message::AbortingOther
+            };
+            let aborting = self.config.get_catalog().format(
+                message,
+                &[
+                    ("count", &self.errors_written.to_string()),
+                    ("more", &self.suppressed.to_string()),
+                ],
+            );
+            self.write_str(&aborting)?;
+            self.write_str("\n")?;
+        }
+        let summary = match &self.footer_format {
+            Some(format) => format(&self.counts),
+            None => self.counts.summary(self.config.get_catalog()),
+        };
+        if let (true, Some(summary)) = (self.footer, summary) {
+            self.write_separator()?;
+            self.write_str(&summary)?;
+            self.write_str("\n")?;
+        }
+        self.writer
+            .flush()
+            .map_err(|err| FriendlyErrorError::IoError(err.kind()))?;
+        Ok(self.counts)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        CodeFormat, ColorChoice, FriendlyCodeSnippet, FriendlyCodeSnippetError, LintLevel,
+        ShortRenderer,
+    };
+    use indoc::indoc;
+
+    fn plain() -> RenderConfig {
+        RenderConfig::new().color(ColorChoice::Never)
+    }
+
+    #[test]
+    fn summary_test() {
+        let mut counts = DiagnosticCounts::new();
+        assert_eq!(counts.summary(&MessageCatalog::new()), None);
+        counts.add(ErrorKind::Error);
+        assert_eq!(
+            counts.summary(&MessageCatalog::new()),
+            Some("1 error emitted".to_string())
+        );
+        counts.add(ErrorKind::Error);
+        counts.add(ErrorKind::Error);
+        counts.add(ErrorKind::Warning);
+        counts.add(ErrorKind::Warning);
+        counts.add(ErrorKind::CodeStyle);
+        assert_eq!(
+            counts.summary(&MessageCatalog::new()),
+            Some("3 errors, 2 warnings, 1 code style issue emitted".to_string())
+        );
+        assert_eq!(counts.get(ErrorKind::Warning), 2);
+        assert_eq!(counts.get_total(), 6);
+    }
+
+    #[test]
+    fn exit_code_test() {
+        let mut counts = DiagnosticCounts::new();
+        counts.add(ErrorKind::Warning);
+        assert_eq!(counts.exit_code(), 0);
+        counts.add(ErrorKind::Error);
+        assert_eq!(counts.exit_code(), 1);
+    }
+
+    #[test]
+    fn emit_test() {
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output).config(plain());
+        emitter
+            .emit(FriendlyError::new().title("variable is undefined"))
+            .unwrap();
+        emitter
+            .emit(
+                FriendlyError::new()
+                    .kind(ErrorKind::Warning)
+                    .title("unused variable"),
+            )
+            .unwrap();
+        assert_eq!(emitter.exit_code(), 1);
+        let counts = emitter.finish().unwrap();
+        assert_eq!(counts.get(ErrorKind::Error), 1);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            indoc! {"
+                --- Error: variable is undefined -----------------------------------------------
+
+                --- Warning: unused variable ---------------------------------------------------
+
+                1 error, 1 warning emitted
+            "}
+        );
+    }
+
+    #[test]
+    fn emit_snippet_test() {
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output).config(plain());
+        emitter
+            .emit(
+                FriendlyError::new()
+                    .title("variable is undefined")
+                    .add_code_snippet(
+                        FriendlyCodeSnippet::new("let x = foo;")
+                            .line_start(1)
+                            .index_start(8)
+                            .line_end(1)
+                            .index_end(11),
+                    ),
+            )
+            .unwrap();
+        emitter.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            indoc! {"
+                --- Error: variable is undefined -----------------------------------------------
+
+                    1:8
+                  1 | let x = foo;
+                    |         ^^^
+
+                1 error emitted
+            "}
+        );
+    }
+
+    #[test]
+    fn footer_format_test() {
+        let footer = |counts: &DiagnosticCounts| match counts.get(ErrorKind::Error) {
+            0 => None,
+            errors => Some(format!("found {} problems", errors)),
+        };
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output)
+            .config(plain())
+            .renderer(ShortRenderer)
+            .separator("")
+            .footer_format(footer);
+        emitter.emit(FriendlyError::new().title("first")).unwrap();
+        emitter.emit(FriendlyError::new().title("second")).unwrap();
+        emitter.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            indoc! {"
+                error: first
+                error: second
+                found 2 problems
+            "}
+        );
+
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output)
+            .config(plain())
+            .renderer(ShortRenderer)
+            .footer_format(footer);
+        emitter
+            .emit(
+                FriendlyError::new()
+                    .kind(ErrorKind::Warning)
+                    .title("unused"),
+            )
+            .unwrap();
+        emitter.finish().unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "warning: unused\n");
+    }
+
+    #[test]
+    fn custom_renderer_test() {
+        let snippet = FriendlyCodeSnippet::new("let x = foo;")
+            .set_file_path("src/main.rs")
+            .line_start(1)
+            .index_start(8)
+            .line_end(1)
+            .index_end(11);
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output)
+            .config(plain())
+            .renderer(ShortRenderer)
+            .separator("")
+            .footer(false);
+        for title in ["variable is undefined", "type mismatch"] {
+            emitter
+                .emit(
+                    FriendlyError::new()
+                        .title(title)
+                        .add_code_snippet(snippet.clone()),
+                )
+                .unwrap();
+        }
+        emitter.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            indoc! {"
+                src/main.rs:1:9: error: variable is undefined
+                src/main.rs:1:9: error: type mismatch
+            "}
+        );
+    }
+
+    fn duplicated_errors() -> Vec<FriendlyError> {
+        let error = FriendlyError::new().title("variable is undefined");
+        vec![
+            error.clone(),
+            FriendlyError::new().title("type mismatch"),
+            error.clone(),
+            error,
+        ]
+    }
+
+    #[test]
+    fn deduplicate_test() {
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output)
+            .config(plain())
+            .renderer(ShortRenderer)
+            .separator("")
+            .deduplicate(DedupKey::new());
+        for error in duplicated_errors() {
+            emitter.emit(error).unwrap();
+        }
+        assert_eq!(emitter.finish().unwrap().get(ErrorKind::Error), 2);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            indoc! {"
+                error: variable is undefined
+                error: type mismatch
+                2 errors emitted
+            "}
+        );
+    }
+
+    #[test]
+    fn report_duplicates_test() {
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output)
+            .config(plain())
+            .renderer(ShortRenderer)
+            .separator("")
+            .footer(false)
+            .deduplicate(DedupKey::new())
+            .report_duplicates(true);
+        for error in duplicated_errors() {
+            emitter.emit(error).unwrap();
+        }
+        emitter.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            indoc! {"
+                error: variable is undefined
+                (reported 3 times)
+                error: type mismatch
+            "}
+        );
+    }
+
+    #[test]
+    fn sort_test() {
+        let error = |path: &str, line: usize, index_start: usize, kind: ErrorKind, code: &str| {
+            FriendlyError::new()
+                .kind(kind)
+                .error_code(code)
+                .title("oops")
+                .add_code_snippet(
+                    FriendlyCodeSnippet::new("let x = foo;\nlet y = bar;")
+                        .set_file_path(path)
+                        .line_start(line)
+                        .index_start(index_start)
+                        .line_end(line)
+                        .index_end(11),
+                )
+        };
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output)
+            .config(plain())
+            .renderer(ShortRenderer)
+            .separator("")
+            .footer(false)
+            .sort(true);
+        for error in [
+            FriendlyError::new().title("no location"),
+            error("b.rs", 1, 8, ErrorKind::Error, "E2"),
+            error("a.rs", 2, 4, ErrorKind::Error, "E1"),
+            error("a.rs", 1, 8, ErrorKind::Warning, "E1"),
+            error("a.rs", 1, 8, ErrorKind::Error, "E2"),
+            error("a.rs", 1, 8, ErrorKind::Error, "E1"),
+            error("a.rs", 1, 4, ErrorKind::Error, "E3"),
+        ] {
+            emitter.emit(error).unwrap();
+        }
+        emitter.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            indoc! {"
+                a.rs:1:5: error[E3]: oops
+                a.rs:1:9: error[E1]: oops
+                a.rs:1:9: error[E2]: oops
+                a.rs:1:9: warning[E1]: oops
+                a.rs:2:5: error[E1]: oops
+                b.rs:1:9: error[E2]: oops
+                error: no location
+            "}
+        );
+    }
+
+    /// Only writes the title, so snippets are never validated while rendering.
+    struct TitleRenderer;
+
+    impl Renderer for TitleRenderer {
+        fn render_to(
+            &self,
+            error: &FriendlyError,
+            _config: &RenderConfig,
+            output: &mut dyn std::fmt::Write,
+        ) -> Result<(), FriendlyErrorError> {
+            output.write_str(error.get_title().unwrap_or_default())?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn invalid_sort_key_test() {
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output)
+            .renderer(TitleRenderer)
+            .separator("")
+            .deduplicate(DedupKey::new().primary_span(false))
+            .sort(true);
+        assert_eq!(
+            emitter.emit(
+                FriendlyError::new()
+                    .title("oops")
+                    .add_code_snippet(FriendlyCodeSnippet::new("let x = foo;").line_start(1))
+            ),
+            Err(FriendlyErrorError::CodeSnippetError(
+                FriendlyCodeSnippetError::MissingEndPosition
+            ))
+        );
+        assert_eq!(emitter.get_counts(), DiagnosticCounts::new());
+        emitter.emit(FriendlyError::new().title("oops")).unwrap();
+        emitter.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            indoc! {"
+                oops
+                1 error emitted
+            "}
+        );
+    }
+
+    #[test]
+    fn max_errors_test() {
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output)
+            .config(plain())
+            .renderer(ShortRenderer)
+            .separator("")
+            .max_errors(2);
+        for (kind, title) in [
+            (ErrorKind::Error, "first"),
+            (ErrorKind::Warning, "warning"),
+            (ErrorKind::Error, "second"),
+            (ErrorKind::Error, "third"),
+            (ErrorKind::Warning, "cascading warning"),
+        ] {
+            emitter
+                .emit(FriendlyError::new().kind(kind).title(title))
+                .unwrap();
+        }
+        assert_eq!(emitter.get_suppressed(), 2);
+        let counts = emitter.finish().unwrap();
+        assert_eq!(counts.get(ErrorKind::Error), 3);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            indoc! {"
+                error: first
+                warning: warning
+                error: second
+                aborting after 2 errors; 2 more not shown
+                3 errors, 2 warnings emitted
+            "}
+        );
+    }
+
+    #[test]
+    fn max_errors_skips_rendering_test() {
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output)
+            .config(plain())
+            .renderer(ShortRenderer)
+            .separator("")
+            .max_errors(1);
+        emitter.emit(FriendlyError::new().title("first")).unwrap();
+        assert_eq!(
+            emitter.emit(
+                FriendlyError::new()
+                    .title("invalid")
+                    .add_code_snippet(FriendlyCodeSnippet::new("let x = foo;").line_start(1)),
+            ),
+            Err(FriendlyErrorError::CodeSnippetError(
+                FriendlyCodeSnippetError::MissingEndPosition
+            ))
+        );
+        emitter
+            .emit(FriendlyError::new().title("cascading"))
+            .unwrap();
+        assert_eq!(emitter.get_suppressed(), 1);
+        assert_eq!(emitter.finish().unwrap().get(ErrorKind::Error), 2);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            indoc! {"
+                error: first
+                aborting after 1 error; 1 more not shown
+                2 errors emitted
+            "}
+        );
+    }
+
+    #[test]
+    fn max_errors_zero_test() {
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output)
+            .config(plain())
+            .renderer(ShortRenderer)
+            .separator("")
+            .footer(false)
+            .max_errors(0);
+        assert_eq!(emitter.get_max_errors(), None);
+        emitter.emit(FriendlyError::new().title("first")).unwrap();
+        emitter.finish().unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "error: first\n");
+    }
+
+    #[test]
+    fn max_errors_sorted_test() {
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output)
+            .config(plain())
+            .renderer(ShortRenderer)
+            .separator("")
+            .footer(false)
+            .sort(true)
+            .max_errors(1);
+        for code in ["E2", "E1"] {
+            emitter
+                .emit(FriendlyError::new().error_code(code).title("oops"))
+                .unwrap();
+        }
+        assert_eq!(emitter.get_suppressed(), 0);
+        emitter.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            indoc! {"
+                error[E1]: oops
+                aborting after 1 error; 1 more not shown
+            "}
+        );
+    }
+
+    #[test]
+    fn lint_levels_test() {
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output)
+            .config(plain())
+            .renderer(ShortRenderer)
+            .separator("")
+            .lint_levels(
+                LintLevels::new()
+                    .kind(ErrorKind::Warning, LintLevel::Deny)
+                    .code("S1", LintLevel::Allow),
+            );
+        emitter
+            .emit(
+                FriendlyError::new()
+                    .kind(ErrorKind::Warning)
+                    .title("unused variable"),
+            )
+            .unwrap();
+        emitter
+            .emit(
+                FriendlyError::new()
+                    .kind(ErrorKind::CodeStyle)
+                    .error_code("S1")
+                    .title("use snake case"),
+            )
+            .unwrap();
+        assert_eq!(emitter.exit_code(), 1);
+        emitter.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            indoc! {"
+                error: unused variable
+                1 error emitted
+            "}
+        );
+    }
+
+    #[test]
+    fn catalog_test() {
+        let catalog = MessageCatalog::parse(indoc! {"
+            error-count-other = {count} Fehler
+            summary-footer = {counts} ausgegeben
+            aborting-one = Abbruch nach {count} Fehler; {more} weitere nicht angezeigt
+            reported-times = ({count}-mal gemeldet)
+        "})
+        .unwrap();
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output)
+            .config(plain().catalog(catalog))
+            .renderer(ShortRenderer)
+            .separator("")
+            .deduplicate(DedupKey::new())
+            .report_duplicates(true)
+            .max_errors(1);
+        for error in duplicated_errors() {
+            emitter.emit(error).unwrap();
+        }
+        emitter.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            indoc! {"
+                error: variable is undefined
+                (3-mal gemeldet)
+                Abbruch nach 1 Fehler; 1 weitere nicht angezeigt
+                2 Fehler ausgegeben
+            "}
+        );
+    }
+
+    #[test]
+    fn failed_emit_test() {
+        let mut output = Vec::new();
+        let mut emitter = DiagnosticEmitter::new(&mut output)
+            .config(plain().code_format(CodeFormat::new().prefix("E", 4)))
+            .renderer(ShortRenderer);
+        for code in ["E0001", "E12", "E0002"] {
+            let result = emitter.emit(FriendlyError::new().error_code(code).title("oops"));
+            assert_eq!(result.is_err(), code == "E12");
+        }
+        assert_eq!(emitter.finish().unwrap().get_total(), 2);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            indoc! {"
+                error[E0001]: oops
+
+                error[E0002]: oops
+
+                2 errors emitted
+            "}
+        );
+    }
+}
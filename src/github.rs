@@ -0,0 +1,203 @@
+use crate::{
+    ErrorKind, FriendlyCodeSnippetError, FriendlyError, FriendlyErrorError, RenderConfig, Renderer,
+};
+use std::fmt;
+
+/// Renders a [`FriendlyError`] as a GitHub Actions workflow command, so it
+/// shows up as an annotation on the pull request:
+///
+/// ```text
+/// ::error file=src/main.rs,line=1,col=9,endLine=1,endColumn=11,title=E1234%3A variable is undefined::foo is not defined
+/// ```
+///
+/// The location comes from the first code snippet.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct GithubRenderer;
+
+fn get_command(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::Error => "error",
+        ErrorKind::Warning => "warning",
+        ErrorKind::Improvement => "notice",
+        ErrorKind::CodeStyle => "notice",
+    }
+}
+
+fn escape_data(string: &str) -> String {
+    string
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(string: &str) -> String {
+    escape_data(string).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn get_title(error: &FriendlyError) -> Option<String> {
+    match (error.get_error_code(), error.get_title()) {
+        (Some(code), Some(title)) => Some(format!("{}: {}", code, title)),
+        (Some(code), None) => Some(code.to_string()),
+        (None, Some(title)) => Some(title.to_string()),
+        (None, None) => None,
+    }
+}
+
+fn get_message(error: &FriendlyError, config: &RenderConfig) -> String {
+    let mut message = Vec::new();
+    message.extend(error.get_summary().map(String::from));
+    if let Some(snippet) = error.get_code_snippets().first() {
+        message.extend(snippet.get_caption().map(String::from));
+    }
+    message.extend(error.get_description().map(String::from));
+    if message.is_empty() {
+        message.push(
+            error
+                .get_title()
+                .map(String::from)
+                .unwrap_or_else(|| config.get_catalog().get_label(error.get_kind()).to_string()),
+        );
+    }
+    if let Some(url) = error.resolve_doc_url(config) {
+        message.push(config.get_catalog().format_docs_link(&url));
+    }
+    message.join("\n\n")
+}
+
+impl Renderer for GithubRenderer {
+    fn render_to(
+        &self,
+        error: &FriendlyError,
+        config: &RenderConfig,
+        output: &mut dyn fmt::Write,
+    ) -> Result<(), FriendlyErrorError> {
+        let mut properties = Vec::new();
+        if let Some(snippet) = error.get_code_snippets().first() {
+            snippet
+                .validate_inputs()
+                .map_err(FriendlyErrorError::CodeSnippetError)?;
+            if let Some(file_path) = snippet.get_file_path() {
+                properties.push(format!("file={}", escape_property(file_path)));
+            }
+            let position = snippet
+                .get_position()
+                .ok_or(FriendlyErrorError::CodeSnippetError(
+                    FriendlyCodeSnippetError::InvalidEndPosition,
+                ))?;
+            properties.push(format!("line={}", position.line_start));
+            properties.push(format!("col={}", position.column_start + 1));
+            properties.push(format!("endLine={}", position.line_end));
+            properties.push(format!("endColumn={}", position.column_end));
+        }
+        if let Some(title) = get_title(error) {
+            properties.push(format!("title={}", escape_property(&title)));
+        }
+
+        output.write_str("::")?;
+        output.write_str(get_command(error.get_kind()))?;
+        if !properties.is_empty() {
+            output.write_char(' ')?;
+            output.write_str(&properties.join(","))?;
+        }
+        output.write_str("::")?;
+        output.write_str(&escape_data(&get_message(error, config)))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::FriendlyCodeSnippet;
+
+    #[test]
+    fn escape_test() {
+        assert_eq!(escape_data("50%\r\nof: a, b"), "50%25%0D%0Aof: a, b");
+        assert_eq!(
+            escape_property("50%\r\nof: a, b"),
+            "50%25%0D%0Aof%3A a%2C b"
+        );
+    }
+
+    #[test]
+    fn render_test() {
+        let error = FriendlyError::new()
+            .error_code("E1234")
+            .title("variable is undefined")
+            .summary("Variables must be defined before they're used.")
+            .doc_url("https://example.com/")
+            .add_code_snippet(
+                FriendlyCodeSnippet::new("let x = foo;")
+                    .set_file_path("src/main,1.rs")
+                    .line_start(1)
+                    .index_start(8)
+                    .line_end(1)
+                    .index_end(11)
+                    .caption("foo is not defined"),
+            );
+        assert_eq!(
+            GithubRenderer.render(&error, &RenderConfig::default()),
+            Ok(concat!(
+                "::error file=src/main%2C1.rs,line=1,col=9,endLine=1,endColumn=11,",
+                "title=E1234%3A variable is undefined::",
+                "Variables must be defined before they're used.%0A%0A",
+                "foo is not defined%0A%0A",
+                "To learn more, read the docs at https://example.com/"
+            )
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn kind_test() {
+        let render = |kind| {
+            GithubRenderer
+                .render(&FriendlyError::new().kind(kind), &RenderConfig::default())
+                .unwrap()
+        };
+        assert_eq!(render(ErrorKind::Error), "::error::Error");
+        assert_eq!(render(ErrorKind::Warning), "::warning::Warning");
+        assert_eq!(render(ErrorKind::Improvement), "::notice::Improvement");
+        assert_eq!(render(ErrorKind::CodeStyle), "::notice::Code style");
+    }
+
+    #[test]
+    fn fallback_test() {
+        let error = FriendlyError::new().title("50% of: a, b");
+        assert_eq!(
+            GithubRenderer.render(&error, &RenderConfig::default()),
+            Ok("::error title=50%25 of%3A a%2C b::50%25 of: a, b".to_string())
+        );
+        let error = FriendlyError::new()
+            .kind(ErrorKind::Warning)
+            .add_code_snippet(
+                FriendlyCodeSnippet::new("let x = foo;")
+                    .line_start(1)
+                    .index_start(8)
+                    .line_end(1)
+                    .index_end(11),
+            );
+        assert_eq!(
+            GithubRenderer.render(&error, &RenderConfig::default()),
+            Ok("::warning line=1,col=9,endLine=1,endColumn=11::Warning".to_string())
+        );
+    }
+
+    #[test]
+    fn span_past_end_of_line_test() {
+        let error = FriendlyError::new().title("t").add_code_snippet(
+            FriendlyCodeSnippet::new("let x = foo;")
+                .set_file_path("a.rs")
+                .line_start(1)
+                .index_start(8)
+                .line_end(1)
+                .index_end(20),
+        );
+        assert_eq!(
+            GithubRenderer.render(&error, &RenderConfig::default()),
+            Err(FriendlyErrorError::CodeSnippetError(
+                FriendlyCodeSnippetError::InvalidEndPosition
+            ))
+        );
+    }
+}
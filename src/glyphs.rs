@@ -0,0 +1,53 @@
+/// The characters used to draw the header, gutter and span markers.
+///
+/// ASCII is the default since it displays correctly on every terminal.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum GlyphSet {
+    Ascii,
+    Unicode,
+}
+
+pub(crate) struct Glyphs {
+    pub(crate) header_rule: char,
+    pub(crate) gutter: char,
+    pub(crate) underline: char,
+    pub(crate) caption_arrow: &'static str,
+    pub(crate) span_start: char,
+    pub(crate) span_end: char,
+    pub(crate) span_vertical: char,
+    pub(crate) span_horizontal: char,
+    pub(crate) span_pointer: char,
+}
+
+const ASCII: Glyphs = Glyphs {
+    header_rule: '-',
+    gutter: '|',
+    underline: '^',
+    caption_arrow: "-->",
+    span_start: ' ',
+    span_end: '|',
+    span_vertical: '|',
+    span_horizontal: '_',
+    span_pointer: '^',
+};
+
+const UNICODE: Glyphs = Glyphs {
+    header_rule: '─',
+    gutter: '│',
+    underline: '─',
+    caption_arrow: "──>",
+    span_start: '╭',
+    span_end: '╰',
+    span_vertical: '│',
+    span_horizontal: '─',
+    span_pointer: '┘',
+};
+
+impl GlyphSet {
+    pub(crate) fn glyphs(self) -> &'static Glyphs {
+        match self {
+            GlyphSet::Ascii => &ASCII,
+            GlyphSet::Unicode => &UNICODE,
+        }
+    }
+}
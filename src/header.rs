@@ -1,110 +1,175 @@
-use crate::{ErrorKind, FriendlyError};
-use colored::*;
+use crate::output::Output;
+use crate::{ErrorKind, FriendlyError, MessageCatalog, RenderConfig};
+use std::fmt::{self, Write};
 
 const HEADER_LENGTH: usize = 80;
 
-fn get_label(kind: &ErrorKind) -> String {
-    match kind {
-        ErrorKind::Error => "Error".to_string(),
-        ErrorKind::Warning => "Warning".to_string(),
-        ErrorKind::Improvement => "Improvement".to_string(),
-        ErrorKind::CodeStyle => "Code style".to_string(),
-    }
+fn colorize_label(string: String, kind: &ErrorKind, config: &RenderConfig) -> String {
+    config.paint(&string, config.get_theme().get_error_kind(*kind))
 }
 
-fn colorize_label(string: String, kind: &ErrorKind) -> String {
-    match kind {
-        ErrorKind::Error => string.red().bold().to_string(),
-        ErrorKind::Warning => string.yellow().bold().to_string(),
-        ErrorKind::Improvement => string.cyan().bold().to_string(),
-        ErrorKind::CodeStyle => string.cyan().bold().to_string(),
+impl FriendlyError {
+    /// A one-line description of the error for formats that only have room
+    /// for a single message: the title, else the summary, else the kind's
+    /// label from `catalog`.
+    pub(crate) fn get_headline(&self, catalog: &MessageCatalog) -> String {
+        match (self.get_title(), self.get_summary()) {
+            (Some(title), _) => title.to_string(),
+            (None, Some(summary)) => summary.to_string(),
+            (None, None) => catalog.get_label(self.get_kind()).to_string(),
+        }
     }
-}
 
-impl FriendlyError {
-    fn append_label(&mut self) -> usize {
-        let mut output = String::new();
-        output.push_str("--- ");
-        output.push_str(&get_label(&self.data.kind));
-        if let Some(code) = &self.data.error_code {
-            output.push('(');
-            output.push_str(code);
-            output.push(')');
+    fn append_label(
+        &self,
+        config: &RenderConfig,
+        output: &mut Output,
+    ) -> Result<usize, fmt::Error> {
+        let rule = config.glyph_table().header_rule;
+        let mut label = String::new();
+        label.extend([rule, rule, rule, ' ']);
+        label.push_str(config.get_catalog().get_label(self.get_kind()));
+        if let Some(code) = self.get_error_code() {
+            label.push('(');
+            label.push_str(code);
+            label.push(')');
         }
-        let length = output.len();
-        self.output
-            .push_str(&colorize_label(output, &self.data.kind));
-        length
+        let length = label.chars().count();
+        output.write_str(&colorize_label(label, &self.get_kind(), config))?;
+        Ok(length)
     }
 
-    fn append_title(&mut self) -> usize {
-        if let Some(title) = &self.data.title {
-            self.output.push_str(": ");
-            self.output.push_str(title);
-            return title.len() + 2;
+    fn append_title(&self, output: &mut Output) -> Result<usize, fmt::Error> {
+        if let Some(title) = self.get_title() {
+            output.write_str(": ")?;
+            output.write_str(title)?;
+            return Ok(title.chars().count() + 2);
         }
-        0
+        Ok(0)
     }
 
+    #[deprecated(note = "use `FriendlyError::render` or a `Renderer` instead")]
     pub fn print_header(&mut self) {
+        let config = RenderConfig::default().resolved();
+        let _ = self.print_with(|error, output| error.write_header(&config, output));
+    }
+
+    pub(crate) fn write_header(&self, config: &RenderConfig, output: &mut Output) -> fmt::Result {
         let mut header_length = HEADER_LENGTH;
-        header_length -= self.append_label();
-        header_length -= self.append_title();
-        self.output.push(' ');
-        header_length -= 1;
+        header_length = header_length.saturating_sub(self.append_label(config, output)?);
+        header_length = header_length.saturating_sub(self.append_title(output)?);
+        output.write_char(' ')?;
+        header_length = header_length.saturating_sub(1);
         for _ in 0..header_length {
-            self.output.push('-');
+            output.write_char(config.glyph_table().header_rule)?;
         }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{ColorChoice, GlyphSet};
 
     #[test]
     fn header_base_case() {
-        colored::control::set_override(false);
-        let mut error = FriendlyError::new();
-        error.print_header();
+        let error = FriendlyError::new();
+        let mut output = String::new();
+        error
+            .write_header(
+                &RenderConfig::new().color(ColorChoice::Never),
+                &mut Output::new(&mut output),
+            )
+            .unwrap();
         assert_eq!(
-            error.output,
+            output,
             "--- Error ----------------------------------------------------------------------"
         );
     }
 
     #[test]
     fn header_with_title() {
-        colored::control::set_override(false);
-        let mut error = FriendlyError::new().title("Error message");
-        error.print_header();
+        let error = FriendlyError::new().title("Error message");
+        let mut output = String::new();
+        error
+            .write_header(
+                &RenderConfig::new().color(ColorChoice::Never),
+                &mut Output::new(&mut output),
+            )
+            .unwrap();
         assert_eq!(
-            error.output,
+            output,
             "--- Error: Error message -------------------------------------------------------"
         );
     }
 
+    #[test]
+    fn header_with_long_title() {
+        let title = "x".repeat(90);
+        let error = FriendlyError::new().title(&title);
+        let mut output = String::new();
+        error
+            .write_header(
+                &RenderConfig::new().color(ColorChoice::Never),
+                &mut Output::new(&mut output),
+            )
+            .unwrap();
+        assert_eq!(output, format!("--- Error: {} ", title));
+        assert!(error.to_string().starts_with(&output));
+    }
+
     #[test]
     fn header_with_code() {
-        colored::control::set_override(false);
-        let mut error = FriendlyError::new().error_code("E123");
-        error.print_header();
+        let error = FriendlyError::new().error_code("E123");
+        let mut output = String::new();
+        error
+            .write_header(
+                &RenderConfig::new().color(ColorChoice::Never),
+                &mut Output::new(&mut output),
+            )
+            .unwrap();
         assert_eq!(
-            error.output,
+            output,
             "--- Error(E123) ----------------------------------------------------------------"
         );
     }
 
     #[test]
     fn header_with_title_and_code() {
-        colored::control::set_override(false);
-        let mut error = FriendlyError::new()
+        let error = FriendlyError::new()
             .title("Error message")
             .error_code("E123");
-        error.print_header();
+        let mut output = String::new();
+        error
+            .write_header(
+                &RenderConfig::new().color(ColorChoice::Never),
+                &mut Output::new(&mut output),
+            )
+            .unwrap();
         assert_eq!(
-            error.output,
+            output,
             "--- Error(E123): Error message -------------------------------------------------"
         );
     }
+
+    #[test]
+    fn header_with_unicode_glyphs() {
+        let error = FriendlyError::new()
+            .title("Error message")
+            .error_code("E123");
+        let mut output = String::new();
+        error
+            .write_header(
+                &RenderConfig::new()
+                    .color(ColorChoice::Never)
+                    .glyphs(GlyphSet::Unicode),
+                &mut Output::new(&mut output),
+            )
+            .unwrap();
+        assert_eq!(
+            output,
+            "─── Error(E123): Error message ─────────────────────────────────────────────────"
+        );
+    }
 }
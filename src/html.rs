@@ -0,0 +1,318 @@
+use crate::{
+    ErrorKind, FriendlyCodeSnippet, FriendlyCodeSnippetError, FriendlyError, FriendlyErrorError,
+    HighlightKind, Message, RenderConfig, Renderer,
+};
+use std::fmt;
+
+/// A stylesheet for the markup produced by [`HtmlRenderer`], following the
+/// colors in `spec.md`.
+pub const HTML_STYLESHEET: &str = "\
+.friendly-error { font-family: sans-serif; margin: 1em 0; }
+.friendly-error > header { font-weight: bold; border-bottom: 1px solid; }
+.friendly-error.error > header .label { color: #c62828; }
+.friendly-error.warning > header .label { color: #b8860b; }
+.friendly-error.improvement > header .label, .friendly-error.code-style > header .label { color: #00838f; }
+.friendly-error figure { margin: 1em 0; }
+.friendly-error figcaption { color: #757575; font-family: monospace; }
+.friendly-error pre { margin: 0; }
+.friendly-error .line-number { display: inline-block; min-width: 3ch; padding-right: 1ch; margin-right: 1ch; text-align: right; color: #1565c0; border-right: 1px solid #1565c0; user-select: none; }
+.friendly-error mark { background: none; text-decoration: underline wavy; }
+.friendly-error mark.error { color: #c62828; }
+.friendly-error mark.warning { color: #b8860b; }
+.friendly-error mark.info { color: #1565c0; }
+.friendly-error .caption { font-style: italic; }
+";
+
+/// Renders a [`FriendlyError`] as a self-contained `<section>` of HTML.
+///
+/// Every piece of text is escaped. The section has the class
+/// `friendly-error` plus one of `error`, `warning`, `improvement` or
+/// `code-style`, and highlighted code is wrapped in `<mark>` with one of
+/// `error`, `warning` or `info`.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct HtmlRenderer {
+    embed_stylesheet: bool,
+}
+
+pub(crate) fn escape_html(string: &str) -> String {
+    let mut output = String::with_capacity(string.len());
+    for char in string.chars() {
+        match char {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            _ => output.push(char),
+        }
+    }
+    output
+}
+
+fn get_kind_class(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::Error => "error",
+        ErrorKind::Warning => "warning",
+        ErrorKind::Improvement => "improvement",
+        ErrorKind::CodeStyle => "code-style",
+    }
+}
+
+fn get_highlight_class(kind: HighlightKind) -> &'static str {
+    match kind {
+        HighlightKind::Error => "error",
+        HighlightKind::Warning => "warning",
+        HighlightKind::Info => "info",
+    }
+}
+
+fn slice_chars(string: &str, start: usize, end: usize) -> &str {
+    let byte_index = |index: usize| {
+        string
+            .char_indices()
+            .nth(index)
+            .map_or(string.len(), |(offset, _)| offset)
+    };
+    &string[byte_index(start)..byte_index(end.max(start))]
+}
+
+impl HtmlRenderer {
+    pub fn new() -> Self {
+        HtmlRenderer::default()
+    }
+
+    /// Puts [`HTML_STYLESHEET`] in a `<style>` element before the section.
+    pub fn embed_stylesheet(mut self, embed_stylesheet: bool) -> Self {
+        self.embed_stylesheet = embed_stylesheet;
+        self
+    }
+
+    fn write_snippet(
+        &self,
+        snippet: &FriendlyCodeSnippet,
+        output: &mut dyn fmt::Write,
+    ) -> Result<(), FriendlyErrorError> {
+        let position = snippet
+            .get_position()
+            .ok_or(FriendlyErrorError::CodeSnippetError(
+                FriendlyCodeSnippetError::InvalidEndPosition,
+            ))?;
+        let class = get_highlight_class(snippet.get_kind());
+        writeln!(output, "<figure class=\"snippet {}\">", class)?;
+
+        writeln!(
+            output,
+            "<figcaption>{}</figcaption>",
+            escape_html(&snippet.format_location(&position))
+        )?;
+
+        output.write_str("<pre><code>")?;
+        let lines = snippet.get_span_lines();
+        for (index, line) in lines.iter().enumerate() {
+            let line_number = position.line_start + index;
+            let start = match line_number == position.line_start {
+                true => position.column_start,
+                false => 0,
+            };
+            let end = match line_number == position.line_end {
+                true => position.column_end,
+                false => line.chars().count(),
+            };
+            if index > 0 {
+                output.write_char('\n')?;
+            }
+            write!(
+                output,
+                "<span class=\"line-number\">{}</span>{}<mark class=\"{}\">{}</mark>{}",
+                line_number,
+                escape_html(slice_chars(line, 0, start)),
+                class,
+                escape_html(slice_chars(line, start, end)),
+                escape_html(slice_chars(line, end, usize::MAX)),
+            )?;
+        }
+        output.write_str("</code></pre>\n")?;
+
+        if let Some(caption) = snippet.get_caption() {
+            writeln!(output, "<p class=\"caption\">{}</p>", escape_html(caption))?;
+        }
+        output.write_str("</figure>\n")?;
+        Ok(())
+    }
+}
+
+impl Renderer for HtmlRenderer {
+    fn render_to(
+        &self,
+        error: &FriendlyError,
+        config: &RenderConfig,
+        output: &mut dyn fmt::Write,
+    ) -> Result<(), FriendlyErrorError> {
+        let snippets = error.prepare_code_snippets()?;
+        if self.embed_stylesheet {
+            writeln!(output, "<style>\n{}</style>", HTML_STYLESHEET)?;
+        }
+        writeln!(
+            output,
+            "<section class=\"friendly-error {}\">",
+            get_kind_class(error.get_kind())
+        )?;
+
+        let mut label = config.get_catalog().get_label(error.get_kind()).to_string();
+        if let Some(code) = error.get_error_code() {
+            label.push_str(&format!("({})", code));
+        }
+        write!(
+            output,
+            "<header><span class=\"label\">{}</span>",
+            escape_html(&label)
+        )?;
+        if let Some(title) = error.get_title() {
+            write!(
+                output,
+                ": <span class=\"title\">{}</span>",
+                escape_html(title)
+            )?;
+        }
+        output.write_str("</header>\n")?;
+
+        if let Some(summary) = error.get_summary() {
+            writeln!(output, "<p class=\"summary\">{}</p>", escape_html(summary))?;
+        }
+        for (snippet, _) in snippets.iter() {
+            self.write_snippet(snippet, output)?;
+        }
+        if let Some(description) = error.get_description() {
+            writeln!(
+                output,
+                "<p class=\"description\">{}</p>",
+                escape_html(description)
+            )?;
+        }
+        if let Some(url) = error.resolve_doc_url(config) {
+            let url = escape_html(&url);
+            let link = format!("<a href=\"{}\">{}</a>", url, url);
+            let sentence = escape_html(config.get_catalog().get(Message::DocsLink));
+            writeln!(
+                output,
+                "<p class=\"docs\">{}</p>",
+                sentence.replace("{url}", &link)
+            )?;
+        }
+        output.write_str("</section>")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn escape_html_test() {
+        assert_eq!(
+            escape_html("<a href=\"x\">Tom & Jerry's</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn slice_chars_test() {
+        assert_eq!(slice_chars("let café = 1;", 4, 8), "café");
+        assert_eq!(slice_chars("let café = 1;", 8, usize::MAX), " = 1;");
+        assert_eq!(slice_chars("let", 5, 2), "");
+    }
+
+    #[test]
+    fn render_test() {
+        let error = FriendlyError::new()
+            .kind(ErrorKind::Warning)
+            .error_code("E1234")
+            .title("comparison <is> always true")
+            .summary("Both sides are the same.")
+            .add_code_snippet(
+                FriendlyCodeSnippet::new("if a < b && a < b {\n    run();\n}")
+                    .set_file_path("src/main.rs")
+                    .line_start(1)
+                    .index_start(3)
+                    .line_end(1)
+                    .index_end(17)
+                    .kind(HighlightKind::Warning)
+                    .caption("this is the same as `a < b`"),
+            )
+            .doc_url("https://example.com/?a=1&b=2");
+        assert_eq!(
+            HtmlRenderer::new().render(&error, &RenderConfig::default()),
+            Ok(indoc!(
+                r#"
+                <section class="friendly-error warning">
+                <header><span class="label">Warning(E1234)</span>: <span class="title">comparison &lt;is&gt; always true</span></header>
+                <p class="summary">Both sides are the same.</p>
+                <figure class="snippet warning">
+                <figcaption>src/main.rs:1:4</figcaption>
+                <pre><code><span class="line-number">1</span>if <mark class="warning">a &lt; b &amp;&amp; a &lt; b</mark> {</code></pre>
+                <p class="caption">this is the same as `a &lt; b`</p>
+                </figure>
+                <p class="docs">To learn more, read the docs at <a href="https://example.com/?a=1&amp;b=2">https://example.com/?a=1&amp;b=2</a></p>
+                </section>"#
+            )
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn multiline_snippet_test() {
+        let snippet = FriendlyCodeSnippet::new("fn main() {\n    run();\n}")
+            .line_start(1)
+            .index_start(10)
+            .line_end(3)
+            .index_end(1);
+        let mut output = String::new();
+        HtmlRenderer::new()
+            .write_snippet(&snippet, &mut output)
+            .unwrap();
+        assert_eq!(
+            output,
+            indoc!(
+                r#"
+                <figure class="snippet error">
+                <figcaption>1:11</figcaption>
+                <pre><code><span class="line-number">1</span>fn main() <mark class="error">{</mark>
+                <span class="line-number">2</span><mark class="error">    run();</mark>
+                <span class="line-number">3</span><mark class="error">}</mark></code></pre>
+                </figure>
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn span_past_end_of_line_test() {
+        let error = FriendlyError::new().add_code_snippet(
+            FriendlyCodeSnippet::new("let x = foo;")
+                .line_start(1)
+                .index_start(8)
+                .line_end(1)
+                .index_end(20),
+        );
+        assert_eq!(
+            error.render_with(&HtmlRenderer::new(), &RenderConfig::default()),
+            Err(FriendlyErrorError::CodeSnippetError(
+                FriendlyCodeSnippetError::InvalidEndPosition
+            ))
+        );
+    }
+
+    #[test]
+    fn embed_stylesheet_test() {
+        let output = HtmlRenderer::new()
+            .embed_stylesheet(true)
+            .render(&FriendlyError::new(), &RenderConfig::default())
+            .unwrap();
+        assert!(output.starts_with("<style>\n.friendly-error {"));
+        assert!(output.ends_with(
+            "</style>\n<section class=\"friendly-error error\">\n<header><span class=\"label\">Error</span></header>\n</section>"
+        ));
+    }
+}
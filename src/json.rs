@@ -0,0 +1,431 @@
+//! JSON representation of diagnostics, enabled by the `serde` feature.
+//!
+//! The schema follows rustc's `--error-format=json`: lines and columns are
+//! 1-based, byte offsets are 0-based from the start of the file, and every
+//! end position is exclusive.
+//!
+//! Diagnostics can also be read back from JSON, e.g. to print diagnostics
+//! produced by another process in the friendly layout. When reading, `spans`,
+//! the byte offsets and `rendered` may be left out; `text` must hold the
+//! source lines the span covers. Since only those lines are known, byte
+//! offsets aren't preserved when the diagnostic is written out again.
+
+use crate::{
+    ColorChoice, ErrorKind, FriendlyCodeSnippet, FriendlyCodeSnippetError, FriendlyError,
+    FriendlyErrorError, HighlightKind, RenderConfig,
+};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Spans read from JSON are rebuilt with a blank line for every line before
+/// them, so line numbers past this are rejected rather than allocated.
+const MAX_LINE: usize = 10_000_000;
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct JsonDiagnostic {
+    pub kind: ErrorKind,
+    pub code: Option<String>,
+    pub title: Option<String>,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub doc_url: Option<String>,
+    #[serde(default)]
+    pub spans: Vec<JsonSpan>,
+    /// The diagnostic as printed by the default renderer, without colors.
+    #[serde(default)]
+    pub rendered: String,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct JsonSpan {
+    pub file: Option<String>,
+    pub line_start: usize,
+    pub line_end: usize,
+    pub column_start: usize,
+    pub column_end: usize,
+    #[serde(default)]
+    pub byte_start: usize,
+    #[serde(default)]
+    pub byte_end: usize,
+    pub kind: HighlightKind,
+    pub label: Option<String>,
+    /// Every source line the span touches, without line endings.
+    pub text: Vec<String>,
+}
+
+impl JsonSpan {
+    pub fn new(snippet: &FriendlyCodeSnippet) -> Result<Self, FriendlyErrorError> {
+        snippet
+            .validate_inputs()
+            .map_err(FriendlyErrorError::CodeSnippetError)?;
+        let position = snippet
+            .get_position()
+            .ok_or(FriendlyErrorError::CodeSnippetError(
+                FriendlyCodeSnippetError::InvalidEndPosition,
+            ))?;
+        Ok(JsonSpan {
+            file: snippet.get_file_path().map(String::from),
+            line_start: position.line_start,
+            line_end: position.line_end,
+            column_start: position.column_start + 1,
+            column_end: position.column_end + 1,
+            byte_start: position.byte_start,
+            byte_end: position.byte_end,
+            kind: snippet.get_kind(),
+            label: snippet.get_caption().map(String::from),
+            text: snippet
+                .get_span_lines()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        })
+    }
+}
+
+impl JsonDiagnostic {
+    pub fn new(error: &FriendlyError) -> Result<Self, FriendlyErrorError> {
+        Ok(JsonDiagnostic {
+            kind: error.get_kind(),
+            code: error.get_error_code().map(String::from),
+            title: error.get_title().map(String::from),
+            summary: error.get_summary().map(String::from),
+            description: error.get_description().map(String::from),
+            doc_url: error.get_doc_url().map(String::from),
+            spans: error
+                .get_code_snippets()
+                .iter()
+                .map(JsonSpan::new)
+                .collect::<Result<_, _>>()?,
+            rendered: error.render(&RenderConfig::new().color(ColorChoice::Never))?,
+        })
+    }
+}
+
+impl TryFrom<JsonSpan> for FriendlyCodeSnippet {
+    type Error = FriendlyErrorError;
+
+    /// Only the lines in `text` are known, so they're placed at their original
+    /// line numbers with blank lines before them. Fails if the lines are out
+    /// of order, implausibly large, or don't match the number of lines in
+    /// `text`.
+    fn try_from(span: JsonSpan) -> Result<Self, Self::Error> {
+        if span.line_start == 0 || span.line_start > span.line_end || span.line_end > MAX_LINE {
+            return Err(FriendlyErrorError::JsonError(format!(
+                "invalid span lines {}-{}",
+                span.line_start, span.line_end
+            )));
+        }
+        if span.text.len() != span.line_end - span.line_start + 1 {
+            return Err(FriendlyErrorError::JsonError(format!(
+                "span covers lines {}-{}, but has {} lines of text",
+                span.line_start,
+                span.line_end,
+                span.text.len()
+            )));
+        }
+        let mut file_contents = "\n".repeat(span.line_start.saturating_sub(1));
+        file_contents.push_str(&span.text.join("\n"));
+        let mut snippet = FriendlyCodeSnippet::new(file_contents)
+            .line_start(span.line_start)
+            .index_start(span.column_start.saturating_sub(1))
+            .line_end(span.line_end)
+            .index_end(span.column_end.saturating_sub(1))
+            .kind(span.kind);
+        if let Some(file) = span.file {
+            snippet = snippet.set_file_path(file);
+        }
+        if let Some(label) = span.label {
+            snippet = snippet.caption(label);
+        }
+        Ok(snippet)
+    }
+}
+
+impl TryFrom<JsonDiagnostic> for FriendlyError {
+    type Error = FriendlyErrorError;
+
+    fn try_from(diagnostic: JsonDiagnostic) -> Result<Self, Self::Error> {
+        let mut error = FriendlyError::new().kind(diagnostic.kind);
+        if let Some(code) = diagnostic.code {
+            error = error.error_code(code);
+        }
+        if let Some(title) = diagnostic.title {
+            error = error.title(title);
+        }
+        if let Some(summary) = diagnostic.summary {
+            error = error.summary(summary);
+        }
+        if let Some(description) = diagnostic.description {
+            error = error.description(description);
+        }
+        if let Some(doc_url) = diagnostic.doc_url {
+            error = error.doc_url(doc_url);
+        }
+        for span in diagnostic.spans {
+            error = error.add_code_snippet(span.try_into()?);
+        }
+        Ok(error)
+    }
+}
+
+impl Serialize for FriendlyError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        JsonDiagnostic::new(self)
+            .map_err(|err| ser::Error::custom(format!("{:?}", err)))?
+            .serialize(serializer)
+    }
+}
+
+impl Serialize for FriendlyCodeSnippet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        JsonSpan::new(self)
+            .map_err(|err| ser::Error::custom(format!("{:?}", err)))?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FriendlyError {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let diagnostic = JsonDiagnostic::deserialize(deserializer)?;
+        FriendlyError::try_from(diagnostic).map_err(|err| de::Error::custom(format!("{:?}", err)))
+    }
+}
+
+impl<'de> Deserialize<'de> for FriendlyCodeSnippet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let span = JsonSpan::deserialize(deserializer)?;
+        FriendlyCodeSnippet::try_from(span).map_err(|err| de::Error::custom(format!("{:?}", err)))
+    }
+}
+
+impl FriendlyError {
+    pub fn to_json(&self) -> Result<String, FriendlyErrorError> {
+        let diagnostic = JsonDiagnostic::new(self)?;
+        serde_json::to_string(&diagnostic)
+            .map_err(|err| FriendlyErrorError::JsonError(err.to_string()))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, FriendlyErrorError> {
+        serde_json::from_str(json).map_err(|err| FriendlyErrorError::JsonError(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn to_json_test() {
+        let error = FriendlyError::new()
+            .kind(ErrorKind::Warning)
+            .error_code("E1234")
+            .title("variable is undefined")
+            .add_code_snippet(
+                FriendlyCodeSnippet::new("fn main() {\n    let x = foo;\n}")
+                    .set_file_path("src/main.rs")
+                    .line_start(2)
+                    .index_start(12)
+                    .line_end(2)
+                    .index_end(15)
+                    .caption("foo is not defined"),
+            );
+        let json: serde_json::Value = serde_json::from_str(&error.to_json().unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "kind": "warning",
+                "code": "E1234",
+                "title": "variable is undefined",
+                "summary": null,
+                "description": null,
+                "doc_url": null,
+                "spans": [{
+                    "file": "src/main.rs",
+                    "line_start": 2,
+                    "line_end": 2,
+                    "column_start": 13,
+                    "column_end": 16,
+                    "byte_start": 24,
+                    "byte_end": 27,
+                    "kind": "error",
+                    "label": "foo is not defined",
+                    "text": ["    let x = foo;"],
+                }],
+                "rendered": error.render(&RenderConfig::new().color(ColorChoice::Never)).unwrap(),
+            })
+        );
+        assert_eq!(serde_json::to_value(&error).unwrap(), json);
+    }
+
+    #[test]
+    fn multiline_span_test() {
+        let error = FriendlyError::new().add_code_snippet(
+            FriendlyCodeSnippet::new("let 😀 = foo(\n    1);")
+                .line_start(1)
+                .index_start(8)
+                .line_end(2)
+                .index_end(6),
+        );
+        let diagnostic = JsonDiagnostic::new(&error).unwrap();
+        let span = &diagnostic.spans[0];
+        assert_eq!(span.file, None);
+        assert_eq!((span.line_start, span.column_start), (1, 9));
+        assert_eq!((span.line_end, span.column_end), (2, 7));
+        assert_eq!((span.byte_start, span.byte_end), (11, 22));
+        assert_eq!(span.text, vec!["let 😀 = foo(", "    1);"]);
+    }
+
+    #[test]
+    fn non_ascii_test() {
+        let error = FriendlyError::new().add_code_snippet(
+            FriendlyCodeSnippet::new("let é = foo;")
+                .line_start(1)
+                .index_start(8)
+                .line_end(1)
+                .index_end(11),
+        );
+        let diagnostic = JsonDiagnostic::new(&error).unwrap();
+        let span = &diagnostic.spans[0];
+        assert_eq!((span.column_start, span.column_end), (9, 12));
+        assert_eq!((span.byte_start, span.byte_end), (9, 12));
+        assert!(diagnostic
+            .rendered
+            .ends_with("  1 | let é = foo;\n    |         ^^^\n"));
+        assert!(error.to_json().is_ok());
+    }
+
+    #[test]
+    fn from_json_test() {
+        let json = r#"{
+            "kind": "error",
+            "code": "E1234",
+            "title": "variable is undefined",
+            "doc_url": "https://example.com/",
+            "spans": [{
+                "file": "src/main.rs",
+                "line_start": 2,
+                "line_end": 2,
+                "column_start": 13,
+                "column_end": 16,
+                "kind": "error",
+                "text": ["    let x = foo;"]
+            }]
+        }"#;
+        let error = FriendlyError::from_json(json).unwrap();
+        assert_eq!(error.get_error_code(), Some("E1234"));
+        assert_eq!(error.get_summary(), None);
+        assert_eq!(
+            error.render(&RenderConfig::new().color(ColorChoice::Never)),
+            Ok(concat!(
+                "--- Error(E1234): variable is undefined ----------------------------------------\n",
+                "\n",
+                "    src/main.rs:2:12\n",
+                "  2 |     let x = foo;\n",
+                "    |             ^^^\n",
+                "\n",
+                "\n",
+                "To learn more, read the docs at https://example.com/",
+            )
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let error = FriendlyError::new()
+            .kind(ErrorKind::Improvement)
+            .title("unused variable")
+            .summary("x is never read")
+            .add_code_snippet(
+                FriendlyCodeSnippet::new("fn main() {\n    let x = 1;\n}")
+                    .line_start(2)
+                    .index_start(8)
+                    .line_end(2)
+                    .index_end(9)
+                    .kind(HighlightKind::Info)
+                    .caption("declared here"),
+            );
+        let config = RenderConfig::new().color(ColorChoice::Never);
+        let json = error.to_json().unwrap();
+        let parsed = FriendlyError::from_json(&json).unwrap();
+        assert_eq!(parsed.render(&config), error.render(&config));
+        assert_eq!(parsed.get_kind(), ErrorKind::Improvement);
+        assert_eq!(
+            parsed.get_code_snippets()[0].get_kind(),
+            HighlightKind::Info
+        );
+    }
+
+    #[test]
+    fn from_non_ascii_json_test() {
+        let json = r#"{
+            "kind": "error",
+            "code": null,
+            "title": null,
+            "summary": null,
+            "description": null,
+            "doc_url": null,
+            "spans": [{
+                "file": null,
+                "line_start": 1,
+                "line_end": 1,
+                "column_start": 10,
+                "column_end": 13,
+                "kind": "error",
+                "label": null,
+                "text": ["let café = foo;"]
+            }]
+        }"#;
+        let error = FriendlyError::from_json(json).unwrap();
+        assert!(error
+            .render(&RenderConfig::new().color(ColorChoice::Never))
+            .unwrap()
+            .ends_with("  1 | let café = foo;\n    |          ^^^\n"));
+    }
+
+    #[test]
+    fn from_invalid_span_json_test() {
+        let json = |line_start: &str, line_end: &str, text: &str| {
+            format!(
+                r#"{{
+                    "kind": "error",
+                    "code": null,
+                    "title": null,
+                    "summary": null,
+                    "description": null,
+                    "doc_url": null,
+                    "spans": [{{
+                        "file": null,
+                        "line_start": {},
+                        "line_end": {},
+                        "column_start": 1,
+                        "column_end": 2,
+                        "kind": "error",
+                        "label": null,
+                        "text": {}
+                    }}]
+                }}"#,
+                line_start, line_end, text
+            )
+        };
+        for (line_start, line_end, text) in [
+            ("18000000000000000000", "18000000000000000000", r#"["x"]"#),
+            ("20000000", "20000000", r#"["x"]"#),
+            ("0", "0", r#"["x"]"#),
+            ("3", "2", r#"["x"]"#),
+            ("1", "2", r#"["x"]"#),
+        ] {
+            assert!(matches!(
+                FriendlyError::from_json(&json(line_start, line_end, text)),
+                Err(FriendlyErrorError::JsonError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn from_invalid_json_test() {
+        assert!(matches!(
+            FriendlyError::from_json(r#"{"kind": "fatal"}"#),
+            Err(FriendlyErrorError::JsonError(_))
+        ));
+    }
+}
@@ -1,13 +1,69 @@
-pub use code_snippet::{FriendlyCodeSnippet, FriendlyCodeSnippetError};
+pub use catalog::{Message, MessageCatalog};
+pub use code_format::CodeFormat;
+pub use code_snippet::{
+    FriendlyCodeSnippet, FriendlyCodeSnippetError, HighlightKind, SpanPosition,
+};
+pub use color::ColorChoice;
+pub use colored::Color;
+pub use dedup::DedupKey;
+pub use emitter::{DiagnosticCounts, DiagnosticEmitter};
+pub use github::GithubRenderer;
+pub use glyphs::GlyphSet;
+pub use html::{HtmlRenderer, HTML_STYLESHEET};
+#[cfg(feature = "serde")]
+pub use json::{JsonDiagnostic, JsonSpan};
+pub use lint_levels::{LintLevel, LintLevels};
+pub use markdown::MarkdownRenderer;
+use output::{LineTracker, Output};
+pub use registry::{ErrorDefinition, ErrorRegistry};
+pub use render_config::RenderConfig;
+pub use renderer::{FriendlyRenderer, Renderer};
+pub use reporters::{CheckstyleReporter, JunitReporter};
+#[cfg(feature = "sarif")]
+pub use sarif::SarifEmitter;
+pub use short::ShortRenderer;
+use std::fmt::{self, Write};
+use std::io::{self, IsTerminal};
+pub use suppression::{Suppression, SuppressionSyntax, Suppressions};
+pub use theme::{Style, Theme};
 
+mod catalog;
+mod code_format;
 mod code_snippet;
+mod color;
+mod dedup;
 mod description;
 mod doc_url;
+mod emitter;
+mod github;
+mod glyphs;
 mod header;
+mod html;
+#[cfg(feature = "serde")]
+mod json;
+mod lint_levels;
+#[cfg(feature = "lsp")]
+mod lsp;
+mod markdown;
+mod output;
 mod print_snippets;
+mod registry;
+mod render_config;
+mod renderer;
+mod reporters;
+#[cfg(feature = "sarif")]
+mod sarif;
+mod short;
 mod summary;
+mod suppression;
+mod theme;
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum ErrorKind {
     Error,
     Warning,
@@ -17,6 +73,7 @@ pub enum ErrorKind {
 
 #[derive(PartialEq, Debug, Clone)]
 struct ErrorData {
+    code_format: Option<CodeFormat>,
     code_snippets: Vec<FriendlyCodeSnippet>,
     description: Option<String>,
     doc_url: Option<String>,
@@ -26,6 +83,7 @@ struct ErrorData {
     title: Option<String>,
 }
 
+#[derive(PartialEq, Debug, Clone)]
 pub struct FriendlyError {
     data: ErrorData,
     pub(crate) output: String,
@@ -33,7 +91,24 @@ pub struct FriendlyError {
 
 #[derive(PartialEq, Debug, Clone)]
 pub enum FriendlyErrorError {
+    /// A [`MessageCatalog`] couldn't be parsed.
+    CatalogError(String),
     CodeSnippetError(FriendlyCodeSnippetError),
+    /// Two definitions in an [`ErrorRegistry`] share this code.
+    DuplicateErrorCode(String),
+    FormatError,
+    /// This code doesn't match the [`CodeFormat`] it was checked against.
+    InvalidErrorCode(String),
+    IoError(io::ErrorKind),
+    /// JSON couldn't be read or written. Only returned with the `serde`
+    /// feature.
+    JsonError(String),
+}
+
+impl From<fmt::Error> for FriendlyErrorError {
+    fn from(_: fmt::Error) -> Self {
+        FriendlyErrorError::FormatError
+    }
 }
 
 pub type FriendlyErrorResult = Result<String, FriendlyErrorError>;
@@ -43,6 +118,7 @@ impl FriendlyError {
         FriendlyError {
             data: {
                 ErrorData {
+                    code_format: None,
                     code_snippets: Vec::new(),
                     description: None,
                     doc_url: None,
@@ -61,6 +137,15 @@ impl FriendlyError {
         self
     }
 
+    /// Rendering fails with [`FriendlyErrorError::InvalidErrorCode`] if the
+    /// error code doesn't match `code_format`. Unlike
+    /// [`RenderConfig::code_format`], this also applies to
+    /// [`FriendlyError::build`].
+    pub fn code_format(mut self, code_format: CodeFormat) -> Self {
+        self.data.code_format = Some(code_format);
+        self
+    }
+
     pub fn description<S: Into<String>>(mut self, description: S) -> Self {
         self.data.description = Some(description.into());
         self
@@ -91,13 +176,47 @@ impl FriendlyError {
         self
     }
 
-    pub fn build(mut self) -> FriendlyErrorResult {
-        self.print_header();
-        self.print_summary();
-        self.print_code_snippets()?;
-        self.print_description();
-        self.print_doc_url();
-        Ok(self.output)
+    pub fn get_code_format(&self) -> Option<&CodeFormat> {
+        self.data.code_format.as_ref()
+    }
+
+    pub fn get_code_snippets(&self) -> &[FriendlyCodeSnippet] {
+        &self.data.code_snippets
+    }
+
+    pub fn get_description(&self) -> Option<&str> {
+        self.data.description.as_deref()
+    }
+
+    pub fn get_doc_url(&self) -> Option<&str> {
+        self.data.doc_url.as_deref()
+    }
+
+    pub fn get_error_code(&self) -> Option<&str> {
+        self.data.error_code.as_deref()
+    }
+
+    pub fn get_kind(&self) -> ErrorKind {
+        self.data.kind
+    }
+
+    pub fn get_summary(&self) -> Option<&str> {
+        self.data.summary.as_deref()
+    }
+
+    pub fn get_title(&self) -> Option<&str> {
+        self.data.title.as_deref()
+    }
+
+    /// Anything already written by the deprecated `print_*` methods comes
+    /// before the rendered error.
+    pub fn build(self) -> FriendlyErrorResult {
+        self.build_with(&FriendlyRenderer)
+    }
+
+    pub fn build_with<R: Renderer>(self, renderer: &R) -> FriendlyErrorResult {
+        let rendered = self.render_with(renderer, &RenderConfig::default())?;
+        Ok(self.output + &rendered)
     }
 
     #[cfg(test)]
@@ -106,10 +225,95 @@ impl FriendlyError {
         self
     }
 
-    pub(crate) fn add_empty_line(&mut self) {
-        if !self.output.is_empty() {
-            self.output.push('\n');
-            self.output.push('\n');
+    /// Appends to the text kept for [`FriendlyError::build`], for the
+    /// deprecated `print_*` methods. Writing to a `String` can't fail, so a
+    /// `fmt::Result` returned by `write` can be ignored.
+    pub(crate) fn print_with<T, F>(&mut self, write: F) -> T
+    where
+        F: FnOnce(&Self, &mut Output) -> T,
+    {
+        let mut buffer = std::mem::take(&mut self.output);
+        let result = write(self, &mut Output::append(&mut buffer));
+        self.output = buffer;
+        result
+    }
+
+    /// Renders with the default [`FriendlyRenderer`]. Unlike
+    /// [`FriendlyError::build`], the error can be rendered again afterwards.
+    pub fn render(&self, config: &RenderConfig) -> FriendlyErrorResult {
+        self.render_with(&FriendlyRenderer, config)
+    }
+
+    /// Checks the error code against the error's own [`CodeFormat`] and the
+    /// one in `config`, and that every code snippet is valid, then renders
+    /// with `renderer`.
+    pub fn render_with<R: Renderer + ?Sized>(
+        &self,
+        renderer: &R,
+        config: &RenderConfig,
+    ) -> FriendlyErrorResult {
+        self.validate(config)?;
+        renderer.render(self, config)
+    }
+
+    /// The checks every render starts with, so they don't depend on the
+    /// renderer.
+    pub(crate) fn validate(&self, config: &RenderConfig) -> Result<(), FriendlyErrorError> {
+        if let Some(code_format) = self.get_code_format() {
+            code_format.validate(self)?;
+        }
+        config.get_code_format().validate(self)?;
+        for snippet in self.get_code_snippets() {
+            snippet
+                .validate_inputs()
+                .map_err(FriendlyErrorError::CodeSnippetError)?;
+        }
+        Ok(())
+    }
+
+    /// Streams the default rendering into `writer` without building the
+    /// whole message in memory first.
+    pub fn write_to<W: io::Write>(
+        &self,
+        writer: &mut W,
+        config: &RenderConfig,
+    ) -> Result<(), FriendlyErrorError> {
+        self.validate(config)?;
+        FriendlyRenderer.write_to(self, config, writer)
+    }
+
+    /// Writes the default rendering to stderr, ending it with a newline if it
+    /// doesn't already end with one. With [`ColorChoice::Auto`], colors are
+    /// used only if stderr is a terminal.
+    pub fn eprint(&self, config: &RenderConfig) -> Result<(), FriendlyErrorError> {
+        let stderr = io::stderr();
+        let config = config.clone().terminal(stderr.is_terminal());
+        // stderr isn't buffered, so collect the many small writes a render
+        // makes before passing them on.
+        let mut stderr = LineTracker::new(io::BufWriter::new(stderr.lock()));
+        self.write_to(&mut stderr, &config)?;
+        if !stderr.ends_with_newline {
+            io::Write::write_all(&mut stderr, b"\n")
+                .map_err(|err| FriendlyErrorError::IoError(err.kind()))?;
+        }
+        io::Write::flush(&mut stderr).map_err(|err| FriendlyErrorError::IoError(err.kind()))
+    }
+}
+
+/// Renders with the default [`FriendlyRenderer`]. If the error can't be
+/// rendered, e.g. because a code snippet is invalid, only the header and the
+/// reason are printed; use [`FriendlyError::build`] to handle that case.
+impl fmt::Display for FriendlyError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let config = RenderConfig::default();
+        match self.render(&config) {
+            Ok(rendered) => formatter.write_str(&rendered),
+            Err(err) => {
+                let mut output = Output::new(formatter);
+                self.write_header(&config.resolved(), &mut output)?;
+                output.add_empty_line()?;
+                write!(output, "This error couldn't be rendered: {:?}", err)
+            }
         }
     }
 }
@@ -0,0 +1,234 @@
+use crate::{ErrorKind, FriendlyError, Message, MessageCatalog};
+use std::collections::HashMap;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum LintLevel {
+    /// Drops the diagnostic.
+    Allow,
+    /// Reports the diagnostic as a [`ErrorKind::Warning`].
+    Warn,
+    /// Reports the diagnostic as an [`ErrorKind::Error`].
+    Deny,
+    /// Like [`LintLevel::Deny`], but when set for an [`ErrorKind`] it can't be
+    /// relaxed for individual error codes.
+    Forbid,
+}
+
+/// Overrides how diagnostics are reported, keyed by error code or by
+/// [`ErrorKind`]. A level set for a code takes precedence over one set for a
+/// kind, unless the kind is forbidden.
+#[derive(PartialEq, Debug, Clone)]
+pub struct LintLevels {
+    codes: HashMap<String, LintLevel>,
+    kinds: Vec<(ErrorKind, LintLevel)>,
+}
+
+impl LintLevels {
+    pub fn new() -> Self {
+        LintLevels {
+            codes: HashMap::new(),
+            kinds: Vec::new(),
+        }
+    }
+
+    pub fn code<S: Into<String>>(mut self, code: S, level: LintLevel) -> Self {
+        self.codes.insert(code.into(), level);
+        self
+    }
+
+    pub fn kind(mut self, kind: ErrorKind, level: LintLevel) -> Self {
+        self.kinds.retain(|(existing, _)| *existing != kind);
+        self.kinds.push((kind, level));
+        self
+    }
+
+    pub fn get_code(&self, code: &str) -> Option<LintLevel> {
+        self.codes.get(code).copied()
+    }
+
+    pub fn get_kind(&self, kind: ErrorKind) -> Option<LintLevel> {
+        self.kinds
+            .iter()
+            .find(|(existing, _)| *existing == kind)
+            .map(|(_, level)| *level)
+    }
+
+    /// The level that applies to `error`, if any was configured.
+    pub fn get_level(&self, error: &FriendlyError) -> Option<LintLevel> {
+        let kind_level = self.get_kind(error.get_kind());
+        if kind_level == Some(LintLevel::Forbid) {
+            return kind_level;
+        }
+        error
+            .get_error_code()
+            .and_then(|code| self.get_code(code))
+            .or(kind_level)
+    }
+
+    /// Rewrites the kind of `error` according to its level, or returns `None`
+    /// if it's allowed. Diagnostics whose kind changes say so in their
+    /// description, in the language of `catalog`.
+    pub fn apply(&self, error: FriendlyError, catalog: &MessageCatalog) -> Option<FriendlyError> {
+        let (kind, message) = match self.get_level(&error) {
+            None => return Some(error),
+            Some(LintLevel::Allow) => return None,
+            Some(LintLevel::Warn) => (ErrorKind::Warning, Message::WarnedByConfiguration),
+            Some(LintLevel::Deny) => (ErrorKind::Error, Message::DeniedByConfiguration),
+            Some(LintLevel::Forbid) => (ErrorKind::Error, Message::ForbiddenByConfiguration),
+        };
+        if error.get_kind() == kind {
+            return Some(error);
+        }
+        let note = catalog.format(message, &[("label", catalog.get_label(error.get_kind()))]);
+        let description = match error.get_description() {
+            Some(description) => format!("{}\n\n{}", description, note),
+            None => note,
+        };
+        Some(error.kind(kind).description(description))
+    }
+}
+
+impl Default for LintLevels {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn get_level_test() {
+        let levels = LintLevels::new()
+            .kind(ErrorKind::Warning, LintLevel::Deny)
+            .kind(ErrorKind::CodeStyle, LintLevel::Forbid)
+            .code("W1", LintLevel::Allow)
+            .code("S1", LintLevel::Allow);
+        let warning = FriendlyError::new().kind(ErrorKind::Warning);
+        assert_eq!(levels.get_level(&warning), Some(LintLevel::Deny));
+        assert_eq!(
+            levels.get_level(&warning.error_code("W1")),
+            Some(LintLevel::Allow)
+        );
+        let style = FriendlyError::new()
+            .kind(ErrorKind::CodeStyle)
+            .error_code("S1");
+        assert_eq!(levels.get_level(&style), Some(LintLevel::Forbid));
+        assert_eq!(levels.get_level(&FriendlyError::new()), None);
+    }
+
+    #[test]
+    fn apply_test() {
+        let levels = LintLevels::new()
+            .kind(ErrorKind::Warning, LintLevel::Deny)
+            .kind(ErrorKind::Improvement, LintLevel::Allow)
+            .code("E1", LintLevel::Warn);
+        assert_eq!(
+            levels.apply(
+                FriendlyError::new()
+                    .kind(ErrorKind::Warning)
+                    .description("x is never read."),
+                &MessageCatalog::new()
+            ),
+            Some(
+                FriendlyError::new()
+                    .description("x is never read.\n\nWarning denied by configuration.")
+            )
+        );
+        assert_eq!(
+            levels.apply(
+                FriendlyError::new().kind(ErrorKind::Improvement),
+                &MessageCatalog::new()
+            ),
+            None
+        );
+        assert_eq!(
+            levels.apply(
+                FriendlyError::new().error_code("E1"),
+                &MessageCatalog::new()
+            ),
+            Some(
+                FriendlyError::new()
+                    .kind(ErrorKind::Warning)
+                    .error_code("E1")
+                    .description("Error warned by configuration.")
+            )
+        );
+        assert_eq!(
+            levels.apply(
+                FriendlyError::new().kind(ErrorKind::CodeStyle),
+                &MessageCatalog::new()
+            ),
+            Some(FriendlyError::new().kind(ErrorKind::CodeStyle))
+        );
+    }
+
+    #[test]
+    fn warn_note_test() {
+        let levels = LintLevels::new()
+            .kind(ErrorKind::CodeStyle, LintLevel::Warn)
+            .kind(ErrorKind::Warning, LintLevel::Warn);
+        assert_eq!(
+            levels.apply(
+                FriendlyError::new().kind(ErrorKind::CodeStyle),
+                &MessageCatalog::new()
+            ),
+            Some(
+                FriendlyError::new()
+                    .kind(ErrorKind::Warning)
+                    .description("Code style warned by configuration.")
+            )
+        );
+        assert_eq!(
+            levels.apply(
+                FriendlyError::new().kind(ErrorKind::Warning),
+                &MessageCatalog::new()
+            ),
+            Some(FriendlyError::new().kind(ErrorKind::Warning))
+        );
+    }
+
+    #[test]
+    fn code_forbid_over_kind_allow_test() {
+        let levels = LintLevels::new()
+            .kind(ErrorKind::Warning, LintLevel::Allow)
+            .code("W1", LintLevel::Forbid);
+        let warning = FriendlyError::new().kind(ErrorKind::Warning);
+        assert_eq!(levels.get_level(&warning), Some(LintLevel::Allow));
+        assert_eq!(
+            levels.get_level(&warning.clone().error_code("W1")),
+            Some(LintLevel::Forbid)
+        );
+        assert_eq!(
+            levels
+                .apply(warning.error_code("W1"), &MessageCatalog::new())
+                .map(|error| error.get_kind()),
+            Some(ErrorKind::Error)
+        );
+    }
+
+    #[test]
+    fn forbid_note_test() {
+        let levels = LintLevels::new().code("S1", LintLevel::Forbid);
+        let style = FriendlyError::new()
+            .kind(ErrorKind::CodeStyle)
+            .error_code("S1");
+        let error = levels.apply(style.clone(), &MessageCatalog::new()).unwrap();
+        assert_eq!(error.get_kind(), ErrorKind::Error);
+        assert_eq!(
+            error.get_description(),
+            Some("Code style forbidden by configuration.")
+        );
+        let catalog = MessageCatalog::new()
+            .set(Message::CodeStyleLabel, "Stil")
+            .set(
+                Message::ForbiddenByConfiguration,
+                "{label}: durch Konfiguration verboten.",
+            );
+        assert_eq!(
+            levels.apply(style, &catalog).unwrap().get_description(),
+            Some("Stil: durch Konfiguration verboten.")
+        );
+    }
+}
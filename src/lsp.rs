@@ -0,0 +1,283 @@
+//! Conversion into Language Server Protocol diagnostics, enabled by the `lsp`
+//! feature.
+
+use crate::{
+    ErrorKind, FriendlyCodeSnippet, FriendlyCodeSnippetError, FriendlyError, FriendlyErrorError,
+    RenderConfig, SpanPosition,
+};
+use lsp_types::{
+    CodeDescription, Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location,
+    NumberOrString, Position, Range, Uri,
+};
+use std::str::FromStr;
+
+fn get_severity(kind: ErrorKind) -> DiagnosticSeverity {
+    match kind {
+        ErrorKind::Error => DiagnosticSeverity::ERROR,
+        ErrorKind::Warning => DiagnosticSeverity::WARNING,
+        ErrorKind::Improvement => DiagnosticSeverity::INFORMATION,
+        ErrorKind::CodeStyle => DiagnosticSeverity::HINT,
+    }
+}
+
+/// LSP positions count columns in UTF-16 code units.
+fn get_utf16_column(line: &str, column: usize) -> u32 {
+    line.chars()
+        .take(column)
+        .map(char::len_utf16)
+        .sum::<usize>() as u32
+}
+
+fn get_range(snippet: &FriendlyCodeSnippet, position: SpanPosition) -> Range {
+    let lines = snippet.get_span_lines();
+    let first_line = lines.first().copied().unwrap_or_default();
+    let last_line = lines.last().copied().unwrap_or_default();
+    Range::new(
+        Position::new(
+            (position.line_start - 1) as u32,
+            get_utf16_column(first_line, position.column_start),
+        ),
+        Position::new(
+            (position.line_end - 1) as u32,
+            get_utf16_column(last_line, position.column_end),
+        ),
+    )
+}
+
+fn get_snippet_range(snippet: &FriendlyCodeSnippet) -> Result<Range, FriendlyErrorError> {
+    snippet
+        .validate_inputs()
+        .map_err(FriendlyErrorError::CodeSnippetError)?;
+    let position = snippet
+        .get_position()
+        .ok_or(FriendlyErrorError::CodeSnippetError(
+            FriendlyCodeSnippetError::InvalidEndPosition,
+        ))?;
+    Ok(get_range(snippet, position))
+}
+
+impl FriendlyError {
+    /// Converts the error into an LSP [`Diagnostic`]. The first code snippet
+    /// gives the range, and the others become related information.
+    ///
+    /// Related information needs a URI for every snippet's file, which
+    /// `file_uri` provides from the snippet's file path. Snippets it returns
+    /// `None` for, or that have no file path, are left out.
+    pub fn to_lsp_diagnostic<F>(&self, file_uri: F) -> Result<Diagnostic, FriendlyErrorError>
+    where
+        F: Fn(&str) -> Option<Uri>,
+    {
+        self.to_lsp_diagnostic_with(&RenderConfig::default(), file_uri)
+    }
+
+    /// Like [`FriendlyError::to_lsp_diagnostic`], but errors without a
+    /// `doc_url` get their code description from the config's doc URL
+    /// template.
+    pub fn to_lsp_diagnostic_with<F>(
+        &self,
+        config: &RenderConfig,
+        file_uri: F,
+    ) -> Result<Diagnostic, FriendlyErrorError>
+    where
+        F: Fn(&str) -> Option<Uri>,
+    {
+        let snippets = self.get_code_snippets();
+        let range = match snippets.first() {
+            Some(snippet) => get_snippet_range(snippet)?,
+            None => Range::default(),
+        };
+
+        let mut related_information = Vec::new();
+        for snippet in snippets.iter().skip(1) {
+            let range = get_snippet_range(snippet)?;
+            let uri = snippet.get_file_path().and_then(&file_uri);
+            if let Some(uri) = uri {
+                related_information.push(DiagnosticRelatedInformation {
+                    location: Location::new(uri, range),
+                    message: snippet.get_caption().unwrap_or_default().to_string(),
+                });
+            }
+        }
+
+        let mut message = Vec::new();
+        message.extend(self.get_title());
+        message.extend(self.get_summary());
+        if let Some(caption) = snippets.first().and_then(|snippet| snippet.get_caption()) {
+            message.push(caption);
+        }
+        let message = if message.is_empty() {
+            self.get_headline(config.get_catalog())
+        } else {
+            message.join("\n")
+        };
+
+        Ok(Diagnostic {
+            range,
+            severity: Some(get_severity(self.get_kind())),
+            code: self
+                .get_error_code()
+                .map(|code| NumberOrString::String(code.to_string())),
+            code_description: self
+                .resolve_doc_url(config)
+                .and_then(|url| Uri::from_str(&url).ok())
+                .map(|href| CodeDescription { href }),
+            message,
+            related_information: match related_information.is_empty() {
+                true => None,
+                false => Some(related_information),
+            },
+            ..Diagnostic::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Message, MessageCatalog};
+
+    fn file_uri(path: &str) -> Option<Uri> {
+        Uri::from_str(&format!("file:///project/{}", path)).ok()
+    }
+
+    #[test]
+    fn get_utf16_column_test() {
+        assert_eq!(get_utf16_column("let x = foo;", 8), 8);
+        assert_eq!(get_utf16_column("let 😀 = foo;", 6), 7);
+        assert_eq!(get_utf16_column("let x", 10), 5);
+    }
+
+    #[test]
+    fn to_lsp_diagnostic_test() {
+        let code = "fn main() {\n    let 😀 = foo;\n}";
+        let error = FriendlyError::new()
+            .kind(ErrorKind::Warning)
+            .error_code("E1234")
+            .title("variable is undefined")
+            .doc_url("https://example.com/E1234")
+            .add_code_snippet(
+                FriendlyCodeSnippet::new(code)
+                    .set_file_path("src/main.rs")
+                    .line_start(2)
+                    .index_start(11)
+                    .line_end(2)
+                    .index_end(14)
+                    .caption("foo is not defined"),
+            )
+            .add_code_snippet(
+                FriendlyCodeSnippet::new(code)
+                    .set_file_path("src/main.rs")
+                    .line_start(1)
+                    .index_start(3)
+                    .line_end(1)
+                    .index_end(7)
+                    .caption("in this function"),
+            )
+            .add_code_snippet(
+                FriendlyCodeSnippet::new(code)
+                    .line_start(1)
+                    .index_start(0)
+                    .line_end(1)
+                    .index_end(2),
+            );
+        let diagnostic = error.to_lsp_diagnostic(file_uri).unwrap();
+        assert_eq!(
+            diagnostic.range,
+            Range::new(Position::new(1, 12), Position::new(1, 15))
+        );
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(
+            diagnostic.code,
+            Some(NumberOrString::String("E1234".to_string()))
+        );
+        assert_eq!(
+            diagnostic.code_description.unwrap().href.as_str(),
+            "https://example.com/E1234"
+        );
+        assert_eq!(
+            diagnostic.message,
+            "variable is undefined\nfoo is not defined"
+        );
+        assert_eq!(
+            diagnostic.related_information,
+            Some(vec![DiagnosticRelatedInformation {
+                location: Location::new(
+                    file_uri("src/main.rs").unwrap(),
+                    Range::new(Position::new(0, 3), Position::new(0, 7))
+                ),
+                message: "in this function".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn without_snippets_test() {
+        let diagnostic = FriendlyError::new()
+            .kind(ErrorKind::CodeStyle)
+            .summary("use snake case")
+            .to_lsp_diagnostic(file_uri)
+            .unwrap();
+        assert_eq!(diagnostic.range, Range::default());
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::HINT));
+        assert_eq!(diagnostic.message, "use snake case");
+        assert_eq!(diagnostic.related_information, None);
+    }
+
+    #[test]
+    fn doc_url_template_test() {
+        let config = RenderConfig::new().doc_url_template("https://example.com/{code}");
+        let diagnostic = FriendlyError::new()
+            .error_code("E1")
+            .to_lsp_diagnostic_with(&config, file_uri)
+            .unwrap();
+        assert_eq!(
+            diagnostic.code_description,
+            Some(CodeDescription {
+                href: Uri::from_str("https://example.com/E1").unwrap()
+            })
+        );
+    }
+
+    #[test]
+    fn headline_message_test() {
+        let diagnostic = FriendlyError::new()
+            .kind(ErrorKind::Warning)
+            .to_lsp_diagnostic(file_uri)
+            .unwrap();
+        assert_eq!(diagnostic.message, "Warning");
+
+        let config = RenderConfig::new()
+            .catalog(MessageCatalog::new().set(Message::WarningLabel, "Warnung"));
+        let diagnostic = FriendlyError::new()
+            .kind(ErrorKind::Warning)
+            .to_lsp_diagnostic_with(&config, file_uri)
+            .unwrap();
+        assert_eq!(diagnostic.message, "Warnung");
+    }
+
+    #[test]
+    fn span_past_end_of_line_test() {
+        let past_end = FriendlyCodeSnippet::new("let x = foo;")
+            .set_file_path("src/main.rs")
+            .line_start(1)
+            .index_start(8)
+            .line_end(1)
+            .index_end(20);
+        let expected = Err(FriendlyErrorError::CodeSnippetError(
+            FriendlyCodeSnippetError::InvalidEndPosition,
+        ));
+        assert_eq!(
+            FriendlyError::new()
+                .add_code_snippet(past_end.clone())
+                .to_lsp_diagnostic(file_uri),
+            expected
+        );
+        assert_eq!(
+            FriendlyError::new()
+                .add_code_snippet(past_end.clone().index_end(11))
+                .add_code_snippet(past_end)
+                .to_lsp_diagnostic(file_uri),
+            expected
+        );
+    }
+}
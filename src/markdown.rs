@@ -0,0 +1,144 @@
+use crate::{ColorChoice, FriendlyError, FriendlyErrorError, RenderConfig, Renderer};
+use std::fmt;
+
+/// Renders a [`FriendlyError`] as Markdown, e.g. for review bot comments:
+/// a heading with the kind, code and title, the summary and description as
+/// paragraphs, each snippet as a fenced code block with its caret line, and
+/// the doc URL as a link.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct MarkdownRenderer;
+
+fn get_longest_backtick_run(string: &str) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for char in string.chars() {
+        match char {
+            '`' => {
+                current += 1;
+                longest = longest.max(current);
+            }
+            _ => current = 0,
+        }
+    }
+    longest
+}
+
+/// A code span that can't be closed early by backticks in `code`.
+fn inline_code(code: &str) -> String {
+    let fence = "`".repeat(get_longest_backtick_run(code) + 1);
+    let padding = match code.starts_with('`') || code.ends_with('`') {
+        true => " ",
+        false => "",
+    };
+    format!("{}{}{}{}{}", fence, padding, code, padding, fence)
+}
+
+/// A fenced code block that can't be closed early by backticks in `code`.
+fn code_block(code: &str) -> String {
+    let fence = "`".repeat((get_longest_backtick_run(code) + 1).max(3));
+    format!("{}text\n{}\n{}", fence, code, fence)
+}
+
+impl Renderer for MarkdownRenderer {
+    fn render_to(
+        &self,
+        error: &FriendlyError,
+        config: &RenderConfig,
+        output: &mut dyn fmt::Write,
+    ) -> Result<(), FriendlyErrorError> {
+        let snippets = error.prepare_code_snippets()?;
+        let plain = RenderConfig::new()
+            .color(ColorChoice::Never)
+            .glyphs(config.get_glyphs());
+        let mut sections = Vec::new();
+
+        let mut heading = format!("### {}", config.get_catalog().get_label(error.get_kind()));
+        if let Some(code) = error.get_error_code() {
+            heading.push_str(&format!("({})", code));
+        }
+        if let Some(title) = error.get_title() {
+            heading.push_str(&format!(": {}", title));
+        }
+        sections.push(heading);
+        sections.extend(error.get_summary().map(String::from));
+
+        for (snippet, layout) in snippets.iter() {
+            if let Some(position) = snippet.get_position() {
+                sections.push(inline_code(&snippet.format_location(&position)));
+            }
+            let lines = snippet.build_lines(layout, &plain);
+            sections.push(code_block(lines.trim_end_matches('\n')));
+            sections.extend(snippet.get_caption().map(String::from));
+        }
+
+        sections.extend(error.get_description().map(String::from));
+        if let Some(url) = error.resolve_doc_url(config) {
+            sections.push(config.get_catalog().format_docs_link(&format!("<{}>", url)));
+        }
+        output.write_str(&sections.join("\n\n"))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ErrorKind, FriendlyCodeSnippet};
+    use indoc::indoc;
+
+    #[test]
+    fn inline_code_test() {
+        assert_eq!(inline_code("src/main.rs:1:8"), "`src/main.rs:1:8`");
+        assert_eq!(inline_code("a `b` c"), "``a `b` c``");
+        assert_eq!(inline_code("`a`"), "`` `a` ``");
+    }
+
+    #[test]
+    fn code_block_test() {
+        assert_eq!(code_block("let x = 1;"), "```text\nlet x = 1;\n```");
+        assert_eq!(
+            code_block("let s = \"```\";"),
+            "````text\nlet s = \"```\";\n````"
+        );
+    }
+
+    #[test]
+    fn render_test() {
+        let error = FriendlyError::new()
+            .kind(ErrorKind::Warning)
+            .error_code("E1234")
+            .title("unused variable")
+            .summary("x is never read.")
+            .add_code_snippet(
+                FriendlyCodeSnippet::new("let x = ````;")
+                    .set_file_path("src/main.rs")
+                    .line_start(1)
+                    .index_start(4)
+                    .line_end(1)
+                    .index_end(5)
+                    .caption("declared here"),
+            )
+            .doc_url("https://example.com/");
+        assert_eq!(
+            MarkdownRenderer.render(&error, &RenderConfig::default()),
+            Ok(indoc!(
+                "
+                ### Warning(E1234): unused variable
+
+                x is never read.
+
+                `src/main.rs:1:5`
+
+                `````text
+                  1 | let x = ````;
+                    |     ^
+                `````
+
+                declared here
+
+                To learn more, read the docs at <https://example.com/>"
+            )
+            .to_string())
+        );
+    }
+}
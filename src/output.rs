@@ -0,0 +1,142 @@
+use std::fmt;
+use std::io;
+
+/// Wraps the writer a diagnostic is rendered into, keeping track of whether
+/// anything has been written yet so sections can be separated by blank lines.
+pub(crate) struct Output<'a> {
+    writer: &'a mut dyn fmt::Write,
+    is_empty: bool,
+}
+
+impl<'a> Output<'a> {
+    pub(crate) fn new(writer: &'a mut dyn fmt::Write) -> Self {
+        Output {
+            writer,
+            is_empty: true,
+        }
+    }
+
+    /// Continues after whatever is already in `buffer`.
+    pub(crate) fn append(buffer: &'a mut String) -> Self {
+        let is_empty = buffer.is_empty();
+        Output {
+            writer: buffer,
+            is_empty,
+        }
+    }
+
+    pub(crate) fn add_empty_line(&mut self) -> fmt::Result {
+        if self.is_empty {
+            return Ok(());
+        }
+        self.writer.write_str("\n\n")
+    }
+}
+
+impl fmt::Write for Output<'_> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        if !string.is_empty() {
+            self.is_empty = false;
+        }
+        self.writer.write_str(string)
+    }
+}
+
+/// Lets `fmt::Write` based renderers stream into an `io::Write`, holding on to
+/// the underlying I/O error since `fmt::Error` cannot carry one.
+pub(crate) struct IoAdapter<'a> {
+    writer: &'a mut dyn io::Write,
+    pub(crate) error: Option<io::Error>,
+}
+
+impl<'a> IoAdapter<'a> {
+    pub(crate) fn new(writer: &'a mut dyn io::Write) -> Self {
+        IoAdapter {
+            writer,
+            error: None,
+        }
+    }
+}
+
+impl fmt::Write for IoAdapter<'_> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        self.writer.write_all(string.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+/// Passes writes through to `writer`, remembering whether the last byte
+/// written was a newline.
+pub(crate) struct LineTracker<W> {
+    writer: W,
+    pub(crate) ends_with_newline: bool,
+}
+
+impl<W: io::Write> LineTracker<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        LineTracker {
+            writer,
+            ends_with_newline: false,
+        }
+    }
+}
+
+impl<W: io::Write> io::Write for LineTracker<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        if written > 0 {
+            self.ends_with_newline = buf[written - 1] == b'\n';
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fmt::Write;
+
+    #[test]
+    fn add_empty_line_test() {
+        let mut buffer = String::new();
+        let mut output = Output::new(&mut buffer);
+        output.add_empty_line().unwrap();
+        output.write_str("first").unwrap();
+        output.add_empty_line().unwrap();
+        output.write_str("second").unwrap();
+        assert_eq!(buffer, "first\n\nsecond");
+    }
+
+    #[test]
+    fn io_adapter_test() {
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut adapter = IoAdapter::new(&mut buffer);
+        adapter.write_str("hello").unwrap();
+        assert!(adapter.error.is_none());
+        assert_eq!(buffer, b"hello");
+    }
+
+    #[test]
+    fn line_tracker_test() {
+        let mut tracker = LineTracker::new(Vec::new());
+        io::Write::write_all(&mut tracker, b"first\n").unwrap();
+        assert!(tracker.ends_with_newline);
+        io::Write::write_all(&mut tracker, b"second").unwrap();
+        assert!(!tracker.ends_with_newline);
+    }
+
+    #[test]
+    fn append_test() {
+        let mut buffer = String::from("first");
+        let mut output = Output::append(&mut buffer);
+        output.add_empty_line().unwrap();
+        output.write_str("second").unwrap();
+        assert_eq!(buffer, "first\n\nsecond");
+    }
+}
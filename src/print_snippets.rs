@@ -1,18 +1,98 @@
-use crate::{FriendlyError, FriendlyErrorError};
+use crate::code_snippet::SnippetLayout;
+use crate::output::Output;
+use crate::{FriendlyCodeSnippet, FriendlyError, FriendlyErrorError, RenderConfig};
+use std::fmt;
 
 impl FriendlyError {
+    /// Validates every snippet up front so nothing is written for a
+    /// diagnostic that can't be rendered in full.
+    pub(crate) fn prepare_code_snippets(
+        &self,
+    ) -> Result<Vec<(&FriendlyCodeSnippet, SnippetLayout)>, FriendlyErrorError> {
+        self.get_code_snippets()
+            .iter()
+            .map(|snippet| match snippet.validate_inputs() {
+                Ok(layout) => Ok((snippet, layout)),
+                Err(err) => Err(FriendlyErrorError::CodeSnippetError(err)),
+            })
+            .collect()
+    }
+
+    /// Nothing is written unless every snippet is valid.
+    #[deprecated(note = "use `FriendlyError::render` or a `Renderer` instead")]
     pub fn print_code_snippets(&mut self) -> Result<bool, FriendlyErrorError> {
-        let snippets = self.data.code_snippets.clone();
-        for snippet in snippets.iter() {
-            let output = snippet.clone().build();
-            match output {
-                Ok(output) => {
-                    self.add_empty_line();
-                    self.output.push_str(&output);
-                }
-                Err(err) => return Err(FriendlyErrorError::CodeSnippetError(err)),
-            }
+        let config = RenderConfig::default().resolved();
+        self.print_with(|error, output| {
+            let snippets = error.prepare_code_snippets()?;
+            FriendlyError::write_code_snippets(&snippets, &config, output)?;
+            Ok(true)
+        })
+    }
+
+    pub(crate) fn write_code_snippets(
+        snippets: &[(&FriendlyCodeSnippet, SnippetLayout)],
+        config: &RenderConfig,
+        output: &mut Output,
+    ) -> fmt::Result {
+        for (snippet, layout) in snippets.iter() {
+            output.add_empty_line()?;
+            snippet.write_to(layout, config, output)?;
         }
-        Ok(true)
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_print_code_snippets() {
+        let mut error = FriendlyError::new()
+            .add_code_snippet(
+                FriendlyCodeSnippet::new("let x = foo;")
+                    .set_file_path("src/main.rs")
+                    .line_start(1)
+                    .index_start(8)
+                    .line_end(1)
+                    .index_end(11),
+            )
+            .set_output("Error message");
+        assert_eq!(error.print_code_snippets(), Ok(true));
+        assert_eq!(
+            error.output,
+            indoc!(
+                "
+                Error message
+
+                    src/main.rs:1:8
+                  1 | let x = foo;
+                    |         ^^^
+                "
+            )
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_print_invalid_code_snippets() {
+        let mut error = FriendlyError::new()
+            .add_code_snippet(FriendlyCodeSnippet::new("let x = foo;"))
+            .set_output("Error message");
+        assert!(error.print_code_snippets().is_err());
+        assert_eq!(error.output, "Error message");
+    }
+
+    #[test]
+    fn test_build_keeps_output() {
+        let error = FriendlyError::new()
+            .title("Error message")
+            .set_output("Earlier output\n");
+        assert!(error
+            .build()
+            .unwrap()
+            .starts_with("Earlier output\n--- Error: Error message"));
     }
 }
@@ -0,0 +1,322 @@
+use crate::{CodeFormat, ErrorKind, FriendlyError, FriendlyErrorError, RenderConfig};
+
+/// Everything that's always the same for diagnostics with a given error code.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ErrorDefinition {
+    code: String,
+    doc_url: Option<String>,
+    explanation: Option<String>,
+    kind: ErrorKind,
+    title: Option<String>,
+}
+
+impl ErrorDefinition {
+    pub fn new<S: Into<String>>(code: S) -> Self {
+        ErrorDefinition {
+            code: code.into(),
+            doc_url: None,
+            explanation: None,
+            kind: ErrorKind::Error,
+            title: None,
+        }
+    }
+
+    pub fn doc_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.doc_url = Some(url.into());
+        self
+    }
+
+    /// The long-form explanation printed by [`ErrorRegistry::explain`].
+    pub fn explanation<S: Into<String>>(mut self, explanation: S) -> Self {
+        self.explanation = Some(explanation.into());
+        self
+    }
+
+    pub fn kind(mut self, kind: ErrorKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// A title template such as `` variable `{name}` is undefined ``. The
+    /// placeholders are filled in by [`FriendlyError::from_code`].
+    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn get_code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn get_doc_url(&self) -> Option<&str> {
+        self.doc_url.as_deref()
+    }
+
+    pub fn get_explanation(&self) -> Option<&str> {
+        self.explanation.as_deref()
+    }
+
+    pub fn get_kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn get_title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// The title template with every `{name}` replaced by its value in
+    /// `args`. Placeholders missing from `args` are left as they are.
+    pub fn format_title(&self, args: &[(&str, &str)]) -> Option<String> {
+        let mut title = self.title.clone()?;
+        for (name, value) in args {
+            title = title.replace(&format!("{{{}}}", name), value);
+        }
+        Some(title)
+    }
+}
+
+/// The error codes a tool can emit, so diagnostics can be created from a code
+/// and explained at length, like `rustc --explain`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ErrorRegistry {
+    code_format: Option<CodeFormat>,
+    definitions: Vec<ErrorDefinition>,
+}
+
+impl ErrorRegistry {
+    pub fn new() -> Self {
+        ErrorRegistry {
+            code_format: None,
+            definitions: Vec::new(),
+        }
+    }
+
+    /// Passed on to every error created with [`FriendlyError::from_code`], so
+    /// that [`FriendlyError::build`] rejects codes that don't match it.
+    pub fn code_format(mut self, code_format: CodeFormat) -> Self {
+        self.code_format = Some(code_format);
+        self
+    }
+
+    pub fn get_code_format(&self) -> Option<&CodeFormat> {
+        self.code_format.as_ref()
+    }
+
+    pub fn register(mut self, definition: ErrorDefinition) -> Self {
+        self.definitions.push(definition);
+        self
+    }
+
+    pub fn get(&self, code: &str) -> Option<&ErrorDefinition> {
+        self.definitions
+            .iter()
+            .find(|definition| definition.code == code)
+    }
+
+    pub fn get_definitions(&self) -> &[ErrorDefinition] {
+        &self.definitions
+    }
+
+    /// Checks that no code is registered twice and that every code matches
+    /// `format`.
+    pub fn validate(&self, format: &CodeFormat) -> Result<(), FriendlyErrorError> {
+        for (index, definition) in self.definitions.iter().enumerate() {
+            if !format.matches(&definition.code) {
+                return Err(FriendlyErrorError::InvalidErrorCode(
+                    definition.code.clone(),
+                ));
+            }
+            if self.definitions[..index]
+                .iter()
+                .any(|earlier| earlier.code == definition.code)
+            {
+                return Err(FriendlyErrorError::DuplicateErrorCode(
+                    definition.code.clone(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The title, long-form explanation and docs link for `code`, or `None`
+    /// if the code isn't registered. Definitions without a `doc_url` are
+    /// linked through the config's doc URL template, and the sentence around
+    /// the link comes from its catalog.
+    pub fn explain(&self, code: &str, config: &RenderConfig) -> Option<String> {
+        let definition = self.get(code)?;
+        let mut sections = vec![match definition.get_title() {
+            Some(title) => format!("{}: {}", definition.code, title),
+            None => definition.code.clone(),
+        }];
+        if let Some(explanation) = definition.get_explanation() {
+            sections.push(explanation.to_string());
+        }
+        let doc_url = match definition.get_doc_url() {
+            Some(doc_url) => Some(doc_url.to_string()),
+            None => config.format_doc_url(&definition.code),
+        };
+        if let Some(doc_url) = doc_url {
+            sections.push(config.get_catalog().format_docs_link(&doc_url));
+        }
+        Some(sections.join("\n\n"))
+    }
+}
+
+impl Default for ErrorRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FriendlyError {
+    /// Creates an error with the given code, filling in the kind, title and
+    /// docs link from its definition in `registry`. `args` fill in the
+    /// placeholders of the title template. Unregistered codes only set the
+    /// error code and the registry's [`CodeFormat`].
+    pub fn from_code<S: Into<String>>(
+        registry: &ErrorRegistry,
+        code: S,
+        args: &[(&str, &str)],
+    ) -> Self {
+        let code = code.into();
+        let mut error = FriendlyError::new();
+        if let Some(code_format) = registry.get_code_format() {
+            error = error.code_format(code_format.clone());
+        }
+        if let Some(definition) = registry.get(&code) {
+            error = error.kind(definition.get_kind());
+            if let Some(title) = definition.format_title(args) {
+                error = error.title(title);
+            }
+            if let Some(doc_url) = definition.get_doc_url() {
+                error = error.doc_url(doc_url);
+            }
+        }
+        error.error_code(code)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+
+    fn registry() -> ErrorRegistry {
+        ErrorRegistry::new()
+            .register(
+                ErrorDefinition::new("E1234")
+                    .title("variable `{name}` is undefined")
+                    .explanation("Variables must be defined before they're used.")
+                    .doc_url("https://docs.example.com/errors/E1234"),
+            )
+            .register(
+                ErrorDefinition::new("W0042")
+                    .kind(ErrorKind::Warning)
+                    .title("unused variable"),
+            )
+    }
+
+    #[test]
+    fn from_code_test() {
+        assert_eq!(
+            FriendlyError::from_code(&registry(), "E1234", &[("name", "foo")]),
+            FriendlyError::new()
+                .error_code("E1234")
+                .title("variable `foo` is undefined")
+                .doc_url("https://docs.example.com/errors/E1234")
+        );
+        assert_eq!(
+            FriendlyError::from_code(&registry(), "W0042", &[]),
+            FriendlyError::new()
+                .kind(ErrorKind::Warning)
+                .error_code("W0042")
+                .title("unused variable")
+        );
+        assert_eq!(
+            FriendlyError::from_code(&registry(), "E9999", &[]),
+            FriendlyError::new().error_code("E9999")
+        );
+    }
+
+    #[test]
+    fn format_title_test() {
+        let definition = ErrorDefinition::new("E1").title("expected {expected}, found {found}");
+        assert_eq!(
+            definition.format_title(&[("expected", "i32")]),
+            Some("expected i32, found {found}".to_string())
+        );
+        assert_eq!(ErrorDefinition::new("E1").format_title(&[]), None);
+    }
+
+    #[test]
+    fn validate_test() {
+        let format = CodeFormat::new().prefix("E", 4).prefix("W", 4);
+        assert_eq!(registry().validate(&format), Ok(()));
+        assert_eq!(
+            registry()
+                .register(ErrorDefinition::new("E1234"))
+                .validate(&CodeFormat::new()),
+            Err(FriendlyErrorError::DuplicateErrorCode("E1234".to_string()))
+        );
+        assert_eq!(
+            registry()
+                .register(ErrorDefinition::new("E12"))
+                .validate(&format),
+            Err(FriendlyErrorError::InvalidErrorCode("E12".to_string()))
+        );
+    }
+
+    #[test]
+    fn from_code_format_test() {
+        let registry = registry().code_format(CodeFormat::new().prefix("E", 4));
+        assert_eq!(
+            FriendlyError::from_code(&registry, "E12", &[]).build(),
+            Err(FriendlyErrorError::InvalidErrorCode("E12".to_string()))
+        );
+        assert!(
+            FriendlyError::from_code(&registry, "E1234", &[("name", "foo")])
+                .build()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn explain_test() {
+        assert_eq!(
+            registry().explain("E1234", &RenderConfig::new()),
+            Some(
+                indoc! {"
+                    E1234: variable `{name}` is undefined
+
+                    Variables must be defined before they're used.
+
+                    To learn more, read the docs at https://docs.example.com/errors/E1234"}
+                .to_string()
+            )
+        );
+        assert_eq!(
+            registry().explain("W0042", &RenderConfig::new()),
+            Some("W0042: unused variable".to_string())
+        );
+        assert_eq!(registry().explain("E9999", &RenderConfig::new()), None);
+    }
+
+    #[test]
+    fn explain_doc_url_template_test() {
+        let config = RenderConfig::new().doc_url_template("https://example.com/{code}");
+        assert_eq!(
+            registry().explain("W0042", &config),
+            Some(
+                indoc! {"
+                    W0042: unused variable
+
+                    To learn more, read the docs at https://example.com/W0042"}
+                .to_string()
+            )
+        );
+        assert!(registry()
+            .explain("E1234", &config)
+            .unwrap()
+            .ends_with("https://docs.example.com/errors/E1234"));
+    }
+}
@@ -0,0 +1,133 @@
+use crate::glyphs::Glyphs;
+use crate::{CodeFormat, ColorChoice, GlyphSet, MessageCatalog, Style, Theme};
+
+/// Options that apply to a single render of a [`FriendlyError`](crate::FriendlyError).
+///
+/// The same error can be rendered any number of times with different
+/// configurations, e.g. once colored for the terminal and once plain for a
+/// log file.
+#[derive(PartialEq, Debug, Clone)]
+pub struct RenderConfig {
+    catalog: MessageCatalog,
+    code_format: CodeFormat,
+    color: ColorChoice,
+    doc_url_template: Option<String>,
+    is_terminal: bool,
+    theme: Theme,
+    glyphs: GlyphSet,
+}
+
+impl RenderConfig {
+    pub fn new() -> Self {
+        RenderConfig {
+            catalog: MessageCatalog::new(),
+            code_format: CodeFormat::new(),
+            color: ColorChoice::Auto,
+            doc_url_template: None,
+            is_terminal: false,
+            theme: Theme::default(),
+            glyphs: GlyphSet::Ascii,
+        }
+    }
+
+    /// Translates the labels and sentences renderers print around an error.
+    pub fn catalog(mut self, catalog: MessageCatalog) -> Self {
+        self.catalog = catalog;
+        self
+    }
+
+    /// Rendering fails with
+    /// [`FriendlyErrorError::InvalidErrorCode`](crate::FriendlyErrorError::InvalidErrorCode)
+    /// if an error's code doesn't match `code_format`. Any code is accepted
+    /// by default.
+    pub fn code_format(mut self, code_format: CodeFormat) -> Self {
+        self.code_format = code_format;
+        self
+    }
+
+    pub fn color(mut self, color: ColorChoice) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Tells [`ColorChoice::Auto`] whether the output is going to a terminal,
+    /// e.g. `.terminal(std::io::stderr().is_terminal())`.
+    pub fn terminal(mut self, is_terminal: bool) -> Self {
+        self.is_terminal = is_terminal;
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn glyphs(mut self, glyphs: GlyphSet) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    /// Builds a docs link for errors that have an error code but no
+    /// `doc_url`, e.g. `https://docs.example.com/errors/{code}`. `{code}` is
+    /// replaced by the error code.
+    pub fn doc_url_template<S: Into<String>>(mut self, template: S) -> Self {
+        self.doc_url_template = Some(template.into());
+        self
+    }
+
+    pub fn get_catalog(&self) -> &MessageCatalog {
+        &self.catalog
+    }
+
+    pub fn get_code_format(&self) -> &CodeFormat {
+        &self.code_format
+    }
+
+    pub fn get_color(&self) -> ColorChoice {
+        self.color
+    }
+
+    pub fn get_doc_url_template(&self) -> Option<&str> {
+        self.doc_url_template.as_deref()
+    }
+
+    pub fn get_terminal(&self) -> bool {
+        self.is_terminal
+    }
+
+    pub fn get_theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    pub fn get_glyphs(&self) -> GlyphSet {
+        self.glyphs
+    }
+
+    pub fn should_colorize(&self) -> bool {
+        self.color.should_colorize(self.is_terminal)
+    }
+
+    /// Settles [`ColorChoice::Auto`] once so a render doesn't consult the
+    /// environment for every piece of text it paints.
+    pub(crate) fn resolved(&self) -> RenderConfig {
+        let color = match self.should_colorize() {
+            true => ColorChoice::Always,
+            false => ColorChoice::Never,
+        };
+        self.clone().color(color)
+    }
+
+    pub(crate) fn glyph_table(&self) -> &'static Glyphs {
+        self.glyphs.glyphs()
+    }
+
+    pub(crate) fn paint(&self, string: &str, style: Style) -> String {
+        style.paint(string, self.should_colorize())
+    }
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
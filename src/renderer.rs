@@ -0,0 +1,313 @@
+use crate::output::{IoAdapter, Output};
+use crate::{FriendlyError, FriendlyErrorError, FriendlyErrorResult, RenderConfig};
+use std::fmt;
+use std::io;
+
+/// Turns a [`FriendlyError`] into its final textual form.
+///
+/// Implement this to output diagnostics in a format other than the default
+/// friendly layout. Everything a renderer needs is available through the
+/// `get_*` accessors on [`FriendlyError`] and
+/// [`FriendlyCodeSnippet`](crate::FriendlyCodeSnippet).
+pub trait Renderer {
+    /// Streams the rendered diagnostic into `output`.
+    fn render_to(
+        &self,
+        error: &FriendlyError,
+        config: &RenderConfig,
+        output: &mut dyn fmt::Write,
+    ) -> Result<(), FriendlyErrorError>;
+
+    fn render(&self, error: &FriendlyError, config: &RenderConfig) -> FriendlyErrorResult {
+        let mut output = String::new();
+        self.render_to(error, config, &mut output)?;
+        Ok(output)
+    }
+
+    fn write_to(
+        &self,
+        error: &FriendlyError,
+        config: &RenderConfig,
+        writer: &mut dyn io::Write,
+    ) -> Result<(), FriendlyErrorError> {
+        let mut adapter = IoAdapter::new(writer);
+        match self.render_to(error, config, &mut adapter) {
+            Err(FriendlyErrorError::FormatError) => match adapter.error {
+                Some(err) => Err(FriendlyErrorError::IoError(err.kind())),
+                None => Err(FriendlyErrorError::FormatError),
+            },
+            result => result,
+        }
+    }
+}
+
+/// The default layout described in the README: header, summary, code
+/// snippets, description, and a link to the docs. Sections are separated by
+/// one blank line. Each code snippet ends with a newline, so a snippet is
+/// followed by two blank lines, or ends the output with a newline.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct FriendlyRenderer;
+
+impl Renderer for FriendlyRenderer {
+    fn render_to(
+        &self,
+        error: &FriendlyError,
+        config: &RenderConfig,
+        output: &mut dyn fmt::Write,
+    ) -> Result<(), FriendlyErrorError> {
+        let snippets = error.prepare_code_snippets()?;
+        let config = &config.resolved();
+        let mut output = Output::new(output);
+        error.write_header(config, &mut output)?;
+        error.write_summary(&mut output)?;
+        FriendlyError::write_code_snippets(&snippets, config, &mut output)?;
+        error.write_description(&mut output)?;
+        error.write_doc_url(config, &mut output)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        CodeFormat, ColorChoice, ErrorKind, FriendlyCodeSnippet, FriendlyCodeSnippetError, Message,
+        MessageCatalog,
+    };
+    use indoc::indoc;
+
+    struct TitleRenderer;
+
+    impl Renderer for TitleRenderer {
+        fn render_to(
+            &self,
+            error: &FriendlyError,
+            _config: &RenderConfig,
+            output: &mut dyn fmt::Write,
+        ) -> Result<(), FriendlyErrorError> {
+            output.write_str(error.get_title().unwrap_or_default())?;
+            Ok(())
+        }
+    }
+
+    struct FailingWriter;
+
+    impl io::Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn friendly_renderer_test() {
+        let error = FriendlyError::new()
+            .title("Error message")
+            .summary("I am the summary")
+            .add_code_snippet(
+                FriendlyCodeSnippet::new("let x = foo;")
+                    .set_file_path("src/main.rs")
+                    .line_start(1)
+                    .index_start(8)
+                    .line_end(1)
+                    .index_end(11),
+            )
+            .description("I am the description")
+            .doc_url("https://example.com/");
+        assert_eq!(
+            FriendlyRenderer.render(&error, &RenderConfig::new().color(ColorChoice::Never)),
+            Ok(indoc!(
+                "
+                --- Error: Error message -------------------------------------------------------
+
+                I am the summary
+
+                    src/main.rs:1:8
+                  1 | let x = foo;
+                    |         ^^^
+
+
+                I am the description
+
+                To learn more, read the docs at https://example.com/"
+            )
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn snippet_spacing_test() {
+        let snippet = FriendlyCodeSnippet::new("let x = foo;")
+            .line_start(1)
+            .index_start(8)
+            .line_end(1)
+            .index_end(11);
+        let error = FriendlyError::new()
+            .title("Error message")
+            .add_code_snippet(snippet.clone().set_file_path("src/main.rs"))
+            .add_code_snippet(snippet.set_file_path("src/lib.rs"));
+        assert_eq!(
+            FriendlyRenderer.render(&error, &RenderConfig::new().color(ColorChoice::Never)),
+            Ok(indoc!(
+                "
+                --- Error: Error message -------------------------------------------------------
+
+                    src/main.rs:1:8
+                  1 | let x = foo;
+                    |         ^^^
+
+
+                    src/lib.rs:1:8
+                  1 | let x = foo;
+                    |         ^^^
+                "
+            )
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn invalid_snippet_writes_nothing() {
+        let error = FriendlyError::new()
+            .title("Error message")
+            .add_code_snippet(FriendlyCodeSnippet::new("let x = foo;").line_start(1));
+        let mut output = String::new();
+        assert_eq!(
+            FriendlyRenderer.render_to(&error, &RenderConfig::new(), &mut output),
+            Err(FriendlyErrorError::CodeSnippetError(
+                FriendlyCodeSnippetError::MissingEndPosition
+            ))
+        );
+        assert_eq!(output, "");
+    }
+
+    #[test]
+    fn custom_renderer_test() {
+        let error = FriendlyError::new().title("Error message");
+        assert_eq!(
+            error.build_with(&TitleRenderer),
+            Ok("Error message".to_string())
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let error = FriendlyError::new().title("Error message");
+        assert_eq!(
+            error.to_string(),
+            FriendlyRenderer
+                .render(&error, &RenderConfig::default())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn display_invalid_snippet_test() {
+        let error = FriendlyError::new()
+            .title("Error message")
+            .add_code_snippet(FriendlyCodeSnippet::new("x").line_start(1));
+        assert_eq!(
+            error.to_string(),
+            indoc! {"
+                --- Error: Error message -------------------------------------------------------
+
+                This error couldn't be rendered: CodeSnippetError(MissingEndPosition)"}
+        );
+        let error = FriendlyError::new()
+            .title("Error message")
+            .add_code_snippet(FriendlyCodeSnippet::new("x").line_start(1).line_end(1));
+        assert!(error
+            .to_string()
+            .ends_with("This error couldn't be rendered: CodeSnippetError(MissingStartPosition)"));
+    }
+
+    #[test]
+    fn invalid_error_code_test() {
+        let config = RenderConfig::new().code_format(CodeFormat::new().prefix("E", 4));
+        let error = FriendlyError::new().error_code("E12");
+        let expected = Err(FriendlyErrorError::InvalidErrorCode("E12".to_string()));
+        assert_eq!(error.render(&config), expected);
+        assert_eq!(error.render_with(&TitleRenderer, &config), expected);
+        let mut output = Vec::new();
+        assert_eq!(error.write_to(&mut output, &config), expected.map(|_| ()));
+        assert!(output.is_empty());
+        assert!(FriendlyError::new()
+            .error_code("E1234")
+            .render(&config)
+            .is_ok());
+    }
+
+    #[test]
+    fn build_invalid_error_code_test() {
+        let error = FriendlyError::new()
+            .error_code("E12")
+            .code_format(CodeFormat::new().prefix("E", 4));
+        let expected = Err(FriendlyErrorError::InvalidErrorCode("E12".to_string()));
+        assert_eq!(error.clone().build_with(&TitleRenderer), expected);
+        assert_eq!(error.clone().build(), expected);
+        assert_eq!(
+            error.to_string(),
+            indoc! {"
+                --- Error(E12) -----------------------------------------------------------------
+
+                This error couldn't be rendered: InvalidErrorCode(\"E12\")"}
+        );
+    }
+
+    #[test]
+    fn catalog_test() {
+        let catalog = MessageCatalog::new()
+            .set(Message::WarningLabel, "Warnung")
+            .set(Message::DocsLink, "Mehr dazu unter {url}");
+        let error = FriendlyError::new()
+            .kind(ErrorKind::Warning)
+            .error_code("W1")
+            .doc_url("https://example.com/");
+        assert_eq!(
+            error.render(
+                &RenderConfig::new()
+                    .color(ColorChoice::Never)
+                    .catalog(catalog)
+            ),
+            Ok(indoc! {"
+                --- Warnung(W1) ----------------------------------------------------------------
+
+                Mehr dazu unter https://example.com/"}
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn render_is_repeatable() {
+        let error = FriendlyError::new().title("Error message");
+        let plain = error
+            .render(&RenderConfig::new().color(ColorChoice::Never))
+            .unwrap();
+        let colored = error
+            .render(&RenderConfig::new().color(ColorChoice::Always))
+            .unwrap();
+        assert_eq!(
+            plain,
+            "--- Error: Error message -------------------------------------------------------"
+        );
+        assert!(colored.starts_with("\x1b[1;31m--- Error\x1b[0m: Error message"));
+        assert_eq!(error.get_title(), Some("Error message"));
+    }
+
+    #[test]
+    fn write_to_test() {
+        let error = FriendlyError::new().title("Error message");
+        let mut buffer: Vec<u8> = Vec::new();
+        TitleRenderer
+            .write_to(&error, &RenderConfig::new(), &mut buffer)
+            .unwrap();
+        assert_eq!(buffer, b"Error message");
+        assert_eq!(
+            TitleRenderer.write_to(&error, &RenderConfig::new(), &mut FailingWriter),
+            Err(FriendlyErrorError::IoError(io::ErrorKind::BrokenPipe))
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use super::{escape_xml_attribute, get_location, get_severity, group_by_file, XML_DECLARATION};
+use crate::{FriendlyError, FriendlyErrorError, RenderConfig};
+use std::fmt::Write;
+
+/// Reports a batch of [`FriendlyError`]s as Checkstyle XML, with one `<file>`
+/// per file path and one `<error>` per diagnostic.
+#[derive(PartialEq, Debug, Clone)]
+pub struct CheckstyleReporter {
+    config: RenderConfig,
+}
+
+impl CheckstyleReporter {
+    pub fn new() -> Self {
+        CheckstyleReporter {
+            config: RenderConfig::new(),
+        }
+    }
+
+    /// Messages of errors without a title or summary use the kind's label
+    /// from the config's catalog.
+    pub fn config(mut self, config: RenderConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn report(&self, errors: &[FriendlyError]) -> Result<String, FriendlyErrorError> {
+        let mut output = String::from(XML_DECLARATION);
+        output.push_str("<checkstyle version=\"4.3\">\n");
+        for (file, group) in group_by_file(errors) {
+            writeln!(output, "  <file name=\"{}\">", escape_xml_attribute(&file))?;
+            for error in group {
+                output.push_str("    <error");
+                if let Some((line, column)) = get_location(error)? {
+                    write!(output, " line=\"{}\" column=\"{}\"", line, column)?;
+                }
+                write!(
+                    output,
+                    " severity=\"{}\" message=\"{}\"",
+                    get_severity(error.get_kind()),
+                    escape_xml_attribute(&error.get_headline(self.config.get_catalog()))
+                )?;
+                if let Some(code) = error.get_error_code() {
+                    write!(output, " source=\"{}\"", escape_xml_attribute(code))?;
+                }
+                output.push_str("/>\n");
+            }
+            output.push_str("  </file>\n");
+        }
+        output.push_str("</checkstyle>\n");
+        Ok(output)
+    }
+}
+
+impl Default for CheckstyleReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ErrorKind, FriendlyCodeSnippet, Message, MessageCatalog};
+    use indoc::indoc;
+
+    #[test]
+    fn report_test() {
+        let snippet = FriendlyCodeSnippet::new("let x = foo;")
+            .set_file_path("src/main.rs")
+            .line_start(1)
+            .index_start(8)
+            .line_end(1)
+            .index_end(11);
+        let errors = vec![
+            FriendlyError::new()
+                .error_code("E1234")
+                .title("variable \"foo\" is undefined")
+                .add_code_snippet(snippet.clone()),
+            FriendlyError::new()
+                .kind(ErrorKind::CodeStyle)
+                .summary("use snake case"),
+            FriendlyError::new()
+                .kind(ErrorKind::Warning)
+                .title("unused variable")
+                .add_code_snippet(snippet.index_start(4).index_end(5)),
+        ];
+        assert_eq!(
+            CheckstyleReporter::new().report(&errors),
+            Ok(indoc!(
+                r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <checkstyle version="4.3">
+                  <file name="src/main.rs">
+                    <error line="1" column="9" severity="error" message="variable &quot;foo&quot; is undefined" source="E1234"/>
+                    <error line="1" column="5" severity="warning" message="unused variable"/>
+                  </file>
+                  <file name="">
+                    <error severity="info" message="use snake case"/>
+                  </file>
+                </checkstyle>
+                "#
+            )
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn catalog_test() {
+        let config = RenderConfig::new()
+            .catalog(MessageCatalog::new().set(Message::WarningLabel, "Warnung"));
+        let errors = vec![FriendlyError::new().kind(ErrorKind::Warning)];
+        assert!(CheckstyleReporter::new()
+            .config(config)
+            .report(&errors)
+            .unwrap()
+            .contains(r#"<error severity="warning" message="Warnung"/>"#));
+    }
+}
@@ -0,0 +1,195 @@
+use super::{
+    escape_xml, escape_xml_attribute, get_location, get_severity, group_by_file, XML_DECLARATION,
+};
+use crate::{ColorChoice, FriendlyError, FriendlyErrorError, RenderConfig};
+use std::fmt::Write;
+
+/// Reports a batch of [`FriendlyError`]s as JUnit XML, with one
+/// `<testsuite>` per file path and one failing `<testcase>` per diagnostic.
+/// Each failure's `type` is the diagnostic's severity, and its body is the
+/// plain-text rendering of the diagnostic.
+#[derive(PartialEq, Debug, Clone)]
+pub struct JunitReporter {
+    config: RenderConfig,
+    name: String,
+}
+
+impl JunitReporter {
+    pub fn new() -> Self {
+        JunitReporter {
+            config: RenderConfig::new(),
+            name: "friendly-errors".to_string(),
+        }
+    }
+
+    /// Used to render each failure's body, with colors always turned off.
+    pub fn config(mut self, config: RenderConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The name of the top-level `<testsuites>` element.
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    fn write_testcase(
+        &self,
+        config: &RenderConfig,
+        file: &str,
+        error: &FriendlyError,
+        output: &mut String,
+    ) -> Result<(), FriendlyErrorError> {
+        let mut name = String::new();
+        if let Some(code) = error.get_error_code() {
+            write!(name, "{}: ", code)?;
+        }
+        name.push_str(&error.get_headline(config.get_catalog()));
+        if let Some((line, column)) = get_location(error)? {
+            write!(name, " ({}:{})", line, column)?;
+        }
+        writeln!(
+            output,
+            "    <testcase name=\"{}\" classname=\"{}\">",
+            escape_xml_attribute(&name),
+            escape_xml_attribute(file)
+        )?;
+        writeln!(
+            output,
+            "      <failure type=\"{}\" message=\"{}\">{}</failure>",
+            get_severity(error.get_kind()),
+            escape_xml_attribute(&error.get_headline(config.get_catalog())),
+            escape_xml(&error.render(config)?)
+        )?;
+        output.push_str("    </testcase>\n");
+        Ok(())
+    }
+
+    pub fn report(&self, errors: &[FriendlyError]) -> Result<String, FriendlyErrorError> {
+        let config = self.config.clone().color(ColorChoice::Never);
+        let mut output = String::from(XML_DECLARATION);
+        writeln!(
+            output,
+            "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\">",
+            escape_xml_attribute(&self.name),
+            errors.len(),
+            errors.len()
+        )?;
+        for (file, group) in group_by_file(errors) {
+            writeln!(
+                output,
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">",
+                escape_xml_attribute(&file),
+                group.len(),
+                group.len()
+            )?;
+            for error in group {
+                self.write_testcase(&config, &file, error, &mut output)?;
+            }
+            output.push_str("  </testsuite>\n");
+        }
+        output.push_str("</testsuites>\n");
+        Ok(output)
+    }
+}
+
+impl Default for JunitReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ErrorKind, FriendlyCodeSnippet, Message, MessageCatalog};
+    use indoc::indoc;
+
+    #[test]
+    fn report_test() {
+        let errors = vec![FriendlyError::new()
+            .error_code("E1234")
+            .title("variable is undefined")
+            .add_code_snippet(
+                FriendlyCodeSnippet::new("if a < foo {}")
+                    .set_file_path("src/main.rs")
+                    .line_start(1)
+                    .index_start(7)
+                    .line_end(1)
+                    .index_end(10),
+            )];
+        assert_eq!(
+            JunitReporter::new().name("lint").report(&errors),
+            Ok(indoc!(
+                r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <testsuites name="lint" tests="1" failures="1">
+                  <testsuite name="src/main.rs" tests="1" failures="1">
+                    <testcase name="E1234: variable is undefined (1:8)" classname="src/main.rs">
+                      <failure type="error" message="variable is undefined">--- Error(E1234): variable is undefined ----------------------------------------
+
+                    src/main.rs:1:7
+                  1 | if a &lt; foo {}
+                    |        ^^^
+                </failure>
+                    </testcase>
+                  </testsuite>
+                </testsuites>
+                "#
+            )
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn doc_url_template_test() {
+        let errors = vec![FriendlyError::new().error_code("E1").title("oops")];
+        let report = JunitReporter::new()
+            .config(RenderConfig::new().doc_url_template("https://example.com/{code}"))
+            .report(&errors)
+            .unwrap();
+        assert!(report.contains("To learn more, read the docs at https://example.com/E1</failure>"));
+    }
+
+    #[test]
+    fn without_file_test() {
+        let errors = vec![
+            FriendlyError::new()
+                .kind(ErrorKind::Warning)
+                .title("\"a\" & b"),
+            FriendlyError::new().kind(ErrorKind::CodeStyle),
+        ];
+        assert_eq!(
+            JunitReporter::new().report(&errors),
+            Ok(indoc!(
+                r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <testsuites name="friendly-errors" tests="2" failures="2">
+                  <testsuite name="" tests="2" failures="2">
+                    <testcase name="&quot;a&quot; &amp; b" classname="">
+                      <failure type="warning" message="&quot;a&quot; &amp; b">--- Warning: &quot;a&quot; &amp; b -----------------------------------------------------------</failure>
+                    </testcase>
+                    <testcase name="Code style" classname="">
+                      <failure type="info" message="Code style">--- Code style -----------------------------------------------------------------</failure>
+                    </testcase>
+                  </testsuite>
+                </testsuites>
+                "#
+            )
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn catalog_test() {
+        let config = RenderConfig::new()
+            .catalog(MessageCatalog::new().set(Message::WarningLabel, "Warnung"));
+        let errors = vec![FriendlyError::new().kind(ErrorKind::Warning)];
+        assert!(JunitReporter::new()
+            .config(config)
+            .report(&errors)
+            .unwrap()
+            .contains(r#"<testcase name="Warnung" classname="">"#));
+    }
+}
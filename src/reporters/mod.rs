@@ -0,0 +1,111 @@
+pub use checkstyle::CheckstyleReporter;
+pub use junit::JunitReporter;
+
+use crate::html::escape_html;
+use crate::{ErrorKind, FriendlyError, FriendlyErrorError};
+
+mod checkstyle;
+mod junit;
+
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n";
+
+fn escape_xml(string: &str) -> String {
+    escape_html(string)
+}
+
+/// Attribute values also need whitespace escaped, since XML parsers turn
+/// literal line breaks and tabs in attributes into spaces.
+fn escape_xml_attribute(string: &str) -> String {
+    escape_html(string)
+        .replace('\n', "&#10;")
+        .replace('\r', "&#13;")
+        .replace('\t', "&#9;")
+}
+
+fn get_severity(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::Error => "error",
+        ErrorKind::Warning => "warning",
+        ErrorKind::Improvement => "info",
+        ErrorKind::CodeStyle => "info",
+    }
+}
+
+fn get_file(error: &FriendlyError) -> String {
+    error
+        .get_code_snippets()
+        .first()
+        .and_then(|snippet| snippet.get_file_path())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// The line and 1-based column of an error's first code snippet.
+fn get_location(error: &FriendlyError) -> Result<Option<(usize, usize)>, FriendlyErrorError> {
+    match error.get_code_snippets().first() {
+        Some(snippet) => {
+            snippet
+                .validate_inputs()
+                .map_err(FriendlyErrorError::CodeSnippetError)?;
+            Ok(snippet
+                .get_position()
+                .map(|position| (position.line_start, position.column_start + 1)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Groups errors by the file of their first code snippet, keeping files in
+/// the order they first appear. Errors without a file are grouped under an
+/// empty name.
+fn group_by_file(errors: &[FriendlyError]) -> Vec<(String, Vec<&FriendlyError>)> {
+    let mut groups: Vec<(String, Vec<&FriendlyError>)> = Vec::new();
+    for error in errors.iter() {
+        let file = get_file(error);
+        match groups.iter_mut().find(|(name, _)| *name == file) {
+            Some((_, group)) => group.push(error),
+            None => groups.push((file, vec![error])),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::FriendlyCodeSnippet;
+
+    #[test]
+    fn escape_xml_attribute_test() {
+        assert_eq!(
+            escape_xml_attribute("a < b\n\t\"c\""),
+            "a &lt; b&#10;&#9;&quot;c&quot;"
+        );
+    }
+
+    #[test]
+    fn group_by_file_test() {
+        let error = |path: &str| {
+            FriendlyError::new().title(path).add_code_snippet(
+                FriendlyCodeSnippet::new("let x = foo;")
+                    .set_file_path(path)
+                    .line_start(1)
+                    .index_start(8)
+                    .line_end(1)
+                    .index_end(11),
+            )
+        };
+        let errors = vec![
+            error("b.rs"),
+            error("a.rs"),
+            FriendlyError::new(),
+            error("b.rs"),
+        ];
+        let groups = group_by_file(&errors);
+        let names: Vec<(&str, usize)> = groups
+            .iter()
+            .map(|(name, group)| (name.as_str(), group.len()))
+            .collect();
+        assert_eq!(names, vec![("b.rs", 2), ("a.rs", 1), ("", 1)]);
+    }
+}
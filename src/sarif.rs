@@ -0,0 +1,400 @@
+//! SARIF 2.1.0 output, enabled by the `sarif` feature.
+
+use crate::{
+    ErrorKind, FriendlyCodeSnippet, FriendlyCodeSnippetError, FriendlyError, FriendlyErrorError,
+    RenderConfig,
+};
+use serde_json::{json, Map, Value};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Converts a batch of [`FriendlyError`]s into a single SARIF log.
+///
+/// Each distinct error code becomes a rule, and the first code snippet of an
+/// error is its location; any other snippets become related locations.
+/// Snippets without a file path can't be located and are left out.
+#[derive(PartialEq, Debug, Clone)]
+pub struct SarifEmitter {
+    config: RenderConfig,
+    tool_name: String,
+    tool_version: Option<String>,
+    information_uri: Option<String>,
+}
+
+fn get_level(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::Error => "error",
+        ErrorKind::Warning => "warning",
+        ErrorKind::Improvement => "note",
+        ErrorKind::CodeStyle => "note",
+    }
+}
+
+fn build_location(
+    snippet: &FriendlyCodeSnippet,
+    id: usize,
+) -> Result<Option<Value>, FriendlyErrorError> {
+    snippet
+        .validate_inputs()
+        .map_err(FriendlyErrorError::CodeSnippetError)?;
+    let file_path = match snippet.get_file_path() {
+        Some(file_path) => file_path,
+        None => return Ok(None),
+    };
+    let position = snippet
+        .get_position()
+        .ok_or(FriendlyErrorError::CodeSnippetError(
+            FriendlyCodeSnippetError::InvalidEndPosition,
+        ))?;
+    let mut location = json!({
+        "id": id,
+        "physicalLocation": {
+            "artifactLocation": { "uri": file_path },
+            "region": {
+                "startLine": position.line_start,
+                "startColumn": position.column_start + 1,
+                "endLine": position.line_end,
+                "endColumn": position.column_end + 1,
+                "byteOffset": position.byte_start,
+                "byteLength": position.byte_end - position.byte_start,
+                "snippet": {
+                    "text": &snippet.get_file_contents()[position.byte_start..position.byte_end],
+                },
+            },
+            "contextRegion": {
+                "startLine": position.line_start,
+                "endLine": position.line_end,
+                "snippet": { "text": snippet.get_span_lines().join("\n") },
+            },
+        },
+    });
+    if let Some(caption) = snippet.get_caption() {
+        location["message"] = json!({ "text": caption });
+    }
+    Ok(Some(location))
+}
+
+impl SarifEmitter {
+    pub fn new<S: Into<String>>(tool_name: S) -> Self {
+        SarifEmitter {
+            config: RenderConfig::new(),
+            tool_name: tool_name.into(),
+            tool_version: None,
+            information_uri: None,
+        }
+    }
+
+    /// Rules for errors without a `doc_url` get their `helpUri` from the
+    /// config's doc URL template.
+    pub fn config(mut self, config: RenderConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn tool_version<S: Into<String>>(mut self, version: S) -> Self {
+        self.tool_version = Some(version.into());
+        self
+    }
+
+    pub fn information_uri<S: Into<String>>(mut self, uri: S) -> Self {
+        self.information_uri = Some(uri.into());
+        self
+    }
+
+    fn build_rules(&self, errors: &[FriendlyError]) -> Vec<Value> {
+        let mut codes: Vec<&str> = Vec::new();
+        let mut rules = Vec::new();
+        for error in errors.iter() {
+            let code = match error.get_error_code() {
+                Some(code) if !codes.contains(&code) => code,
+                _ => continue,
+            };
+            codes.push(code);
+            let mut rule = json!({
+                "id": code,
+                "defaultConfiguration": { "level": get_level(error.get_kind()) },
+            });
+            if let Some(title) = error.get_title() {
+                rule["shortDescription"] = json!({ "text": title });
+            }
+            if let Some(description) = error.get_description() {
+                rule["fullDescription"] = json!({ "text": description });
+            }
+            if let Some(doc_url) = error.resolve_doc_url(&self.config) {
+                rule["helpUri"] = json!(doc_url);
+            }
+            rules.push(rule);
+        }
+        rules
+    }
+
+    fn build_result(
+        &self,
+        error: &FriendlyError,
+        rules: &[Value],
+    ) -> Result<Value, FriendlyErrorError> {
+        let mut result = json!({
+            "level": get_level(error.get_kind()),
+            "message": { "text": error.get_headline(self.config.get_catalog()) },
+        });
+        if let Some(code) = error.get_error_code() {
+            result["ruleId"] = json!(code);
+            if let Some(index) = rules.iter().position(|rule| rule["id"] == code) {
+                result["ruleIndex"] = json!(index);
+            }
+        }
+        let mut locations = Vec::new();
+        for (id, snippet) in error.get_code_snippets().iter().enumerate() {
+            if let Some(location) = build_location(snippet, id)? {
+                locations.push(location);
+            }
+        }
+        if !locations.is_empty() {
+            let related = locations.split_off(1);
+            result["locations"] = Value::Array(locations);
+            if !related.is_empty() {
+                result["relatedLocations"] = Value::Array(related);
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn to_value(&self, errors: &[FriendlyError]) -> Result<Value, FriendlyErrorError> {
+        let rules = self.build_rules(errors);
+        let results = errors
+            .iter()
+            .map(|error| self.build_result(error, &rules))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut driver = Map::new();
+        driver.insert("name".to_string(), json!(self.tool_name));
+        if let Some(version) = &self.tool_version {
+            driver.insert("version".to_string(), json!(version));
+        }
+        if let Some(uri) = &self.information_uri {
+            driver.insert("informationUri".to_string(), json!(uri));
+        }
+        driver.insert("rules".to_string(), Value::Array(rules));
+        Ok(json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": { "driver": driver },
+                "columnKind": "unicodeCodePoints",
+                "results": results,
+            }],
+        }))
+    }
+
+    pub fn emit(&self, errors: &[FriendlyError]) -> Result<String, FriendlyErrorError> {
+        let log = self.to_value(errors)?;
+        serde_json::to_string_pretty(&log)
+            .map_err(|err| FriendlyErrorError::JsonError(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Message, MessageCatalog};
+
+    fn snippet() -> FriendlyCodeSnippet {
+        FriendlyCodeSnippet::new("fn main() {\n    let x = foo;\n}")
+            .set_file_path("src/main.rs")
+            .line_start(2)
+            .index_start(12)
+            .line_end(2)
+            .index_end(15)
+    }
+
+    #[test]
+    fn to_value_test() {
+        let errors = vec![
+            FriendlyError::new()
+                .error_code("E1234")
+                .title("variable is undefined")
+                .doc_url("https://example.com/E1234")
+                .add_code_snippet(snippet().caption("foo is not defined"))
+                .add_code_snippet(
+                    snippet()
+                        .line_start(1)
+                        .index_start(0)
+                        .line_end(1)
+                        .index_end(2),
+                ),
+            FriendlyError::new()
+                .kind(ErrorKind::CodeStyle)
+                .error_code("E1234")
+                .summary("use snake case"),
+            FriendlyError::new().kind(ErrorKind::Warning),
+        ];
+        let log = SarifEmitter::new("friendly")
+            .tool_version("1.0.0")
+            .to_value(&errors)
+            .unwrap();
+        assert_eq!(
+            log,
+            json!({
+                "$schema": SARIF_SCHEMA,
+                "version": "2.1.0",
+                "runs": [{
+                    "tool": {
+                        "driver": {
+                            "name": "friendly",
+                            "version": "1.0.0",
+                            "rules": [{
+                                "id": "E1234",
+                                "defaultConfiguration": { "level": "error" },
+                                "shortDescription": { "text": "variable is undefined" },
+                                "helpUri": "https://example.com/E1234",
+                            }],
+                        },
+                    },
+                    "columnKind": "unicodeCodePoints",
+                    "results": [
+                        {
+                            "ruleId": "E1234",
+                            "ruleIndex": 0,
+                            "level": "error",
+                            "message": { "text": "variable is undefined" },
+                            "locations": [{
+                                "id": 0,
+                                "message": { "text": "foo is not defined" },
+                                "physicalLocation": {
+                                    "artifactLocation": { "uri": "src/main.rs" },
+                                    "region": {
+                                        "startLine": 2,
+                                        "startColumn": 13,
+                                        "endLine": 2,
+                                        "endColumn": 16,
+                                        "byteOffset": 24,
+                                        "byteLength": 3,
+                                        "snippet": { "text": "foo" },
+                                    },
+                                    "contextRegion": {
+                                        "startLine": 2,
+                                        "endLine": 2,
+                                        "snippet": { "text": "    let x = foo;" },
+                                    },
+                                },
+                            }],
+                            "relatedLocations": [{
+                                "id": 1,
+                                "physicalLocation": {
+                                    "artifactLocation": { "uri": "src/main.rs" },
+                                    "region": {
+                                        "startLine": 1,
+                                        "startColumn": 1,
+                                        "endLine": 1,
+                                        "endColumn": 3,
+                                        "byteOffset": 0,
+                                        "byteLength": 2,
+                                        "snippet": { "text": "fn" },
+                                    },
+                                    "contextRegion": {
+                                        "startLine": 1,
+                                        "endLine": 1,
+                                        "snippet": { "text": "fn main() {" },
+                                    },
+                                },
+                            }],
+                        },
+                        {
+                            "ruleId": "E1234",
+                            "ruleIndex": 0,
+                            "level": "note",
+                            "message": { "text": "use snake case" },
+                        },
+                        {
+                            "level": "warning",
+                            "message": { "text": "Warning" },
+                        },
+                    ],
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn doc_url_template_test() {
+        let errors = vec![
+            FriendlyError::new().error_code("E1"),
+            FriendlyError::new()
+                .error_code("E2")
+                .doc_url("https://example.com/custom"),
+        ];
+        let log = SarifEmitter::new("friendly")
+            .config(RenderConfig::new().doc_url_template("https://example.com/{code}"))
+            .to_value(&errors)
+            .unwrap();
+        let rules = &log["runs"][0]["tool"]["driver"]["rules"];
+        assert_eq!(rules[0]["helpUri"], "https://example.com/E1");
+        assert_eq!(rules[1]["helpUri"], "https://example.com/custom");
+    }
+
+    #[test]
+    fn unlocated_snippet_test() {
+        let located = FriendlyCodeSnippet::new("let 😀 = foo;")
+            .set_file_path("src/main.rs")
+            .line_start(1)
+            .index_start(4)
+            .line_end(1)
+            .index_end(5);
+        let errors = vec![FriendlyError::new()
+            .add_code_snippet(
+                FriendlyCodeSnippet::new("foo")
+                    .line_start(1)
+                    .index_start(0)
+                    .line_end(1)
+                    .index_end(3),
+            )
+            .add_code_snippet(located)];
+        let log = SarifEmitter::new("friendly").to_value(&errors).unwrap();
+        let result = &log["runs"][0]["results"][0];
+        assert_eq!(result["relatedLocations"], Value::Null);
+        assert_eq!(
+            result["locations"],
+            json!([{
+                "id": 1,
+                "physicalLocation": {
+                    "artifactLocation": { "uri": "src/main.rs" },
+                    "region": {
+                        "startLine": 1,
+                        "startColumn": 5,
+                        "endLine": 1,
+                        "endColumn": 6,
+                        "byteOffset": 4,
+                        "byteLength": 4,
+                        "snippet": { "text": "😀" },
+                    },
+                    "contextRegion": {
+                        "startLine": 1,
+                        "endLine": 1,
+                        "snippet": { "text": "let 😀 = foo;" },
+                    },
+                },
+            }])
+        );
+    }
+
+    #[test]
+    fn span_past_end_of_line_test() {
+        let errors = vec![FriendlyError::new().add_code_snippet(snippet().index_end(30))];
+        assert_eq!(
+            SarifEmitter::new("friendly").to_value(&errors),
+            Err(FriendlyErrorError::CodeSnippetError(
+                FriendlyCodeSnippetError::InvalidEndPosition
+            ))
+        );
+    }
+
+    #[test]
+    fn catalog_test() {
+        let config = RenderConfig::new()
+            .catalog(MessageCatalog::new().set(Message::WarningLabel, "Warnung"));
+        let log = SarifEmitter::new("friendly")
+            .config(config)
+            .to_value(&[FriendlyError::new().kind(ErrorKind::Warning)])
+            .unwrap();
+        assert_eq!(log["runs"][0]["results"][0]["message"]["text"], "Warnung");
+    }
+}
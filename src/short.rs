@@ -0,0 +1,99 @@
+use crate::{FriendlyError, FriendlyErrorError, RenderConfig, Renderer};
+use std::fmt;
+
+/// Renders a [`FriendlyError`] on a single line in the style of GCC and
+/// rustc's `--error-format=short`, for grepping and editor quickfix lists:
+///
+/// ```text
+/// src/main.rs:1:9: error[E1234]: variable is undefined
+/// ```
+///
+/// The location comes from the first code snippet, with 1-based columns.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct ShortRenderer;
+
+impl Renderer for ShortRenderer {
+    fn render_to(
+        &self,
+        error: &FriendlyError,
+        config: &RenderConfig,
+        output: &mut dyn fmt::Write,
+    ) -> Result<(), FriendlyErrorError> {
+        let mut location = None;
+        if let Some(snippet) = error.get_code_snippets().first() {
+            snippet
+                .validate_inputs()
+                .map_err(FriendlyErrorError::CodeSnippetError)?;
+            location = match snippet.get_position() {
+                Some(position) => Some(snippet.format_location(&position)),
+                None => snippet.get_file_path().map(String::from),
+            };
+        }
+        if let Some(location) = location {
+            output.write_str(&location)?;
+            output.write_str(": ")?;
+        }
+        let label = config.get_catalog().get_label(error.get_kind());
+        output.write_str(&label.to_lowercase())?;
+        if let Some(code) = error.get_error_code() {
+            write!(output, "[{}]", code)?;
+        }
+        if let Some(message) = error.get_title().or_else(|| error.get_summary()) {
+            write!(output, ": {}", message)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ErrorKind, FriendlyCodeSnippet, Message, MessageCatalog};
+
+    fn render(error: &FriendlyError) -> String {
+        ShortRenderer
+            .render(error, &RenderConfig::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn render_test() {
+        let snippet = FriendlyCodeSnippet::new("let x = foo;")
+            .line_start(1)
+            .index_start(8)
+            .line_end(1)
+            .index_end(11);
+        assert_eq!(
+            render(
+                &FriendlyError::new()
+                    .error_code("E1234")
+                    .title("variable is undefined")
+                    .add_code_snippet(snippet.clone().set_file_path("src/main.rs"))
+            ),
+            "src/main.rs:1:9: error[E1234]: variable is undefined"
+        );
+        assert_eq!(
+            render(
+                &FriendlyError::new()
+                    .kind(ErrorKind::Warning)
+                    .summary("x is never read")
+                    .add_code_snippet(snippet)
+            ),
+            "1:9: warning: x is never read"
+        );
+        assert_eq!(
+            render(&FriendlyError::new().kind(ErrorKind::CodeStyle)),
+            "code style"
+        );
+    }
+
+    #[test]
+    fn catalog_test() {
+        let config = RenderConfig::new()
+            .catalog(MessageCatalog::new().set(Message::WarningLabel, "Warnung"));
+        assert_eq!(
+            ShortRenderer.render(&FriendlyError::new().kind(ErrorKind::Warning), &config),
+            Ok("warnung".to_string())
+        );
+    }
+}
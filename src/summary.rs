@@ -1,12 +1,19 @@
+use crate::output::Output;
 use crate::FriendlyError;
+use std::fmt::{self, Write};
 
 impl FriendlyError {
+    #[deprecated(note = "use `FriendlyError::render` or a `Renderer` instead")]
     pub fn print_summary(&mut self) {
-        if let Some(url) = &self.data.summary {
-            let url = url.clone();
-            self.add_empty_line();
-            self.output.push_str(&url);
+        let _ = self.print_with(|error, output| error.write_summary(output));
+    }
+
+    pub(crate) fn write_summary(&self, output: &mut Output) -> fmt::Result {
+        if let Some(summary) = self.get_summary() {
+            output.add_empty_line()?;
+            output.write_str(summary)?;
         }
+        Ok(())
     }
 }
 
@@ -17,13 +24,33 @@ mod test {
 
     #[test]
     fn test_append_summary() {
-        let mut error = FriendlyError::new().summary("I am the summary");
-        error.print_summary();
-        assert_eq!(error.output, "I am the summary");
+        let error = FriendlyError::new().summary("I am the summary");
+        let mut output = String::new();
+        error.write_summary(&mut Output::new(&mut output)).unwrap();
+        assert_eq!(output, "I am the summary");
     }
 
     #[test]
     fn test_append_summary_with_output() {
+        let error = FriendlyError::new().summary("I am the summary");
+        let mut output = String::new();
+        let mut writer = Output::new(&mut output);
+        writer.write_str("Error message").unwrap();
+        error.write_summary(&mut writer).unwrap();
+        assert_eq!(
+            output,
+            indoc!(
+                "
+                Error message
+
+                I am the summary"
+            )
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_print_summary() {
         let mut error = FriendlyError::new()
             .summary("I am the summary")
             .set_output("Error message");
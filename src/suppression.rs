@@ -0,0 +1,425 @@
+use crate::{
+    ErrorKind, FriendlyCodeSnippet, FriendlyError, HighlightKind, Message, MessageCatalog,
+};
+
+/// What suppression comments look like in a language. Defaults to
+/// `// friendly-ignore E1234`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct SuppressionSyntax {
+    comment_end: Option<String>,
+    comment_start: String,
+    directive: String,
+}
+
+impl SuppressionSyntax {
+    pub fn new() -> Self {
+        SuppressionSyntax {
+            comment_end: None,
+            comment_start: "//".to_string(),
+            directive: "friendly-ignore".to_string(),
+        }
+    }
+
+    /// Closes block comments, e.g. `*/` or `-->`.
+    pub fn comment_end<S: Into<String>>(mut self, comment_end: S) -> Self {
+        self.comment_end = Some(comment_end.into());
+        self
+    }
+
+    /// Opens the comment, e.g. `#` or `--`.
+    pub fn comment_start<S: Into<String>>(mut self, comment_start: S) -> Self {
+        self.comment_start = comment_start.into();
+        self
+    }
+
+    pub fn directive<S: Into<String>>(mut self, directive: S) -> Self {
+        self.directive = directive.into();
+        self
+    }
+
+    pub fn get_comment_end(&self) -> Option<&str> {
+        self.comment_end.as_deref()
+    }
+
+    pub fn get_comment_start(&self) -> &str {
+        &self.comment_start
+    }
+
+    pub fn get_directive(&self) -> &str {
+        &self.directive
+    }
+
+    /// The column the comment starts at and the codes it lists, if `line`
+    /// contains a suppression comment.
+    fn parse_line(&self, line: &str) -> Option<(usize, Vec<String>)> {
+        for (offset, _) in line.match_indices(&self.comment_start) {
+            let rest = line[offset + self.comment_start.len()..].trim_start();
+            let rest = match rest.strip_prefix(&self.directive) {
+                Some(rest) => rest,
+                None => continue,
+            };
+            let rest = match &self.comment_end {
+                Some(comment_end) => match rest.find(comment_end.as_str()) {
+                    Some(end) => &rest[..end],
+                    None => rest,
+                },
+                None => rest,
+            };
+            if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+                continue;
+            }
+            let codes = rest
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|code| !code.is_empty())
+                .map(String::from)
+                .collect();
+            return Some((line[..offset].chars().count(), codes));
+        }
+        None
+    }
+}
+
+impl Default for SuppressionSyntax {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A code listed in a suppression comment. Lines are 1-based and columns are
+/// 0-based. A comment without codes suppresses everything, and has a `code`
+/// of `None`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Suppression {
+    pub line: usize,
+    pub column: usize,
+    pub code: Option<String>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+struct Entry {
+    suppression: Suppression,
+    target_line: usize,
+    used: bool,
+}
+
+/// The suppression comments in one source file.
+///
+/// A comment applies to its own line. A comment on a line of its own also
+/// applies to the next line of code, so several can be stacked above it:
+///
+/// ```text
+/// // friendly-ignore E1234
+/// // friendly-ignore W0042
+/// let x = foo; // friendly-ignore E0001
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+pub struct Suppressions {
+    entries: Vec<Entry>,
+    file_path: Option<String>,
+    source: String,
+}
+
+impl Suppressions {
+    pub fn new<S: Into<String>>(source: S, syntax: &SuppressionSyntax) -> Self {
+        let source = source.into();
+        let lines: Vec<&str> = source.split('\n').collect();
+        let is_comment_line =
+            |line: &str| line.trim_start().starts_with(syntax.get_comment_start());
+        let mut entries = Vec::new();
+        for (index, line) in lines.iter().enumerate() {
+            let (column, codes) = match syntax.parse_line(line) {
+                Some(parsed) => parsed,
+                None => continue,
+            };
+            let mut target_line = index + 1;
+            if is_comment_line(line) {
+                target_line = lines[index + 1..]
+                    .iter()
+                    .position(|line| !is_comment_line(line))
+                    .map_or(lines.len(), |offset| index + offset + 1)
+                    + 1;
+            }
+            let codes = if codes.is_empty() {
+                vec![None]
+            } else {
+                codes.into_iter().map(Some).collect()
+            };
+            for code in codes {
+                entries.push(Entry {
+                    suppression: Suppression {
+                        line: index + 1,
+                        column,
+                        code,
+                    },
+                    target_line,
+                    used: false,
+                });
+            }
+        }
+        Suppressions {
+            entries,
+            file_path: None,
+            source,
+        }
+    }
+
+    /// The file the comments were read from. [`Suppressions::filter`] then
+    /// leaves errors in other files alone, and
+    /// [`Suppressions::report_unused`] uses it for its code snippets.
+    pub fn set_file_path<S: Into<String>>(mut self, file_path: S) -> Self {
+        self.file_path = Some(file_path.into());
+        self
+    }
+
+    pub fn get_file_path(&self) -> Option<&str> {
+        self.file_path.as_deref()
+    }
+
+    pub fn get_suppressions(&self) -> Vec<&Suppression> {
+        self.entries
+            .iter()
+            .map(|entry| &entry.suppression)
+            .collect()
+    }
+
+    /// Returns `None` if a suppression comment covers the line of the first
+    /// code snippet of `error`. Errors without code snippets, and errors in a
+    /// different file than the one set with [`Suppressions::set_file_path`],
+    /// are never suppressed.
+    pub fn filter(&mut self, error: FriendlyError) -> Option<FriendlyError> {
+        let snippet = match error.get_code_snippets().first() {
+            Some(snippet) => snippet,
+            None => return Some(error),
+        };
+        if let (Some(file_path), Some(snippet_path)) = (&self.file_path, snippet.get_file_path()) {
+            if file_path != snippet_path {
+                return Some(error);
+            }
+        }
+        let line = match snippet.get_line_start() {
+            Some(line) => line,
+            None => return Some(error),
+        };
+        let mut suppressed = false;
+        for entry in self.entries.iter_mut() {
+            let matches_code = match &entry.suppression.code {
+                Some(code) => error.get_error_code() == Some(code.as_str()),
+                None => true,
+            };
+            if entry.target_line == line && matches_code {
+                entry.used = true;
+                suppressed = true;
+            }
+        }
+        if suppressed {
+            return None;
+        }
+        Some(error)
+    }
+
+    /// Suppressions that didn't match any error passed to
+    /// [`Suppressions::filter`].
+    pub fn get_unused(&self) -> Vec<&Suppression> {
+        self.entries
+            .iter()
+            .filter(|entry| !entry.used)
+            .map(|entry| &entry.suppression)
+            .collect()
+    }
+
+    /// A warning pointing at each unused suppression, in the language of
+    /// `catalog`.
+    pub fn report_unused(&self, catalog: &MessageCatalog) -> Vec<FriendlyError> {
+        self.get_unused()
+            .into_iter()
+            .map(|suppression| {
+                let line_length = self
+                    .source
+                    .split('\n')
+                    .nth(suppression.line - 1)
+                    .unwrap_or_default()
+                    .trim_end()
+                    .chars()
+                    .count();
+                let mut snippet = FriendlyCodeSnippet::new(self.source.as_str())
+                    .line_start(suppression.line)
+                    .index_start(suppression.column)
+                    .line_end(suppression.line)
+                    .index_end(line_length)
+                    .kind(HighlightKind::Warning);
+                if let Some(file_path) = &self.file_path {
+                    snippet = snippet.set_file_path(file_path);
+                }
+                let summary = match &suppression.code {
+                    Some(code) => catalog.format(Message::UnusedSuppression, &[("code", code)]),
+                    None => catalog.get(Message::UnusedSuppressionAny).to_string(),
+                };
+                FriendlyError::new()
+                    .kind(ErrorKind::Warning)
+                    .title(catalog.get(Message::UnusedSuppressionTitle))
+                    .summary(summary)
+                    .add_code_snippet(snippet)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ColorChoice, RenderConfig};
+    use indoc::indoc;
+
+    const SOURCE: &str = indoc! {"
+        fn main() {
+            // friendly-ignore E1
+            // friendly-ignore W1, W2
+            let x = foo;
+            let y = bar; // friendly-ignore
+            let z = baz; // friendly-ignored E3
+        }
+    "};
+
+    fn error(code: &str, line: usize) -> FriendlyError {
+        FriendlyError::new().error_code(code).add_code_snippet(
+            FriendlyCodeSnippet::new(SOURCE)
+                .line_start(line)
+                .index_start(4)
+                .line_end(line)
+                .index_end(7),
+        )
+    }
+
+    #[test]
+    fn parse_test() {
+        let suppressions = Suppressions::new(SOURCE, &SuppressionSyntax::new());
+        let found: Vec<(usize, usize, Option<&str>)> = suppressions
+            .get_suppressions()
+            .into_iter()
+            .map(|s| (s.line, s.column, s.code.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (2, 4, Some("E1")),
+                (3, 4, Some("W1")),
+                (3, 4, Some("W2")),
+                (5, 17, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn filter_test() {
+        let mut suppressions = Suppressions::new(SOURCE, &SuppressionSyntax::new());
+        assert_eq!(suppressions.filter(error("E1", 4)), None);
+        assert_eq!(suppressions.filter(error("W2", 4)), None);
+        assert_eq!(suppressions.filter(error("E2", 4)), Some(error("E2", 4)));
+        assert_eq!(suppressions.filter(error("E2", 5)), None);
+        assert_eq!(suppressions.filter(error("E3", 6)), Some(error("E3", 6)));
+        assert_eq!(
+            suppressions.filter(FriendlyError::new()),
+            Some(FriendlyError::new())
+        );
+        let unused: Vec<(usize, Option<&str>)> = suppressions
+            .get_unused()
+            .into_iter()
+            .map(|s| (s.line, s.code.as_deref()))
+            .collect();
+        assert_eq!(unused, vec![(3, Some("W1"))]);
+    }
+
+    #[test]
+    fn other_file_test() {
+        let mut suppressions =
+            Suppressions::new(SOURCE, &SuppressionSyntax::new()).set_file_path("one.rs");
+        let in_file = |path: &str| {
+            FriendlyError::new().error_code("E2").add_code_snippet(
+                FriendlyCodeSnippet::new(SOURCE)
+                    .set_file_path(path)
+                    .line_start(5)
+                    .index_start(4)
+                    .line_end(5)
+                    .index_end(7),
+            )
+        };
+        assert_eq!(
+            suppressions.filter(in_file("two.rs")),
+            Some(in_file("two.rs"))
+        );
+        assert_eq!(suppressions.filter(in_file("one.rs")), None);
+        assert_eq!(suppressions.filter(error("E2", 5)), None);
+    }
+
+    #[test]
+    fn custom_syntax_test() {
+        let source = "<!-- friendly-ignore H1 -->\n<img src=\"a.png\">";
+        let syntax = SuppressionSyntax::new()
+            .comment_start("<!--")
+            .comment_end("-->");
+        let suppressions = Suppressions::new(source, &syntax);
+        assert_eq!(
+            suppressions.get_suppressions(),
+            vec![&Suppression {
+                line: 1,
+                column: 0,
+                code: Some("H1".to_string()),
+            }]
+        );
+        let hash = SuppressionSyntax::new().comment_start("#");
+        assert_eq!(
+            Suppressions::new("x = 1  # friendly-ignore", &hash).get_suppressions(),
+            vec![&Suppression {
+                line: 1,
+                column: 7,
+                code: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn report_unused_test() {
+        let suppressions = Suppressions::new(
+            "let x = 1; // friendly-ignore E1\n",
+            &SuppressionSyntax::new(),
+        )
+        .set_file_path("src/main.rs");
+        let report = suppressions.report_unused(&MessageCatalog::new());
+        assert_eq!(report.len(), 1);
+        assert_eq!(
+            report[0].render(&RenderConfig::new().color(ColorChoice::Never)),
+            Ok(concat!(
+                "--- Warning: unused suppression ------------------------------------------------\n",
+                "\n",
+                "No E1 diagnostic was reported here.\n",
+                "\n",
+                "    src/main.rs:1:11\n",
+                "  1 | let x = 1; // friendly-ignore E1\n",
+                "    |            ^^^^^^^^^^^^^^^^^^^^^\n",
+            )
+            .to_string())
+        );
+    }
+
+    #[test]
+    fn report_unused_non_ascii_test() {
+        let suppressions = Suppressions::new(
+            "let é = 1; // friendly-ignore E1 é\n",
+            &SuppressionSyntax::new(),
+        );
+        let report = suppressions.report_unused(&MessageCatalog::new());
+        assert_eq!(
+            report[0].render(&RenderConfig::new().color(ColorChoice::Never)),
+            Ok(concat!(
+                "--- Warning: unused suppression ------------------------------------------------\n",
+                "\n",
+                "No E1 diagnostic was reported here.\n",
+                "\n",
+                "    1:11\n",
+                "  1 | let é = 1; // friendly-ignore E1 é\n",
+                "    |            ^^^^^^^^^^^^^^^^^^^^^^^\n",
+            )
+            .to_string())
+        );
+    }
+}
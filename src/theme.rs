@@ -0,0 +1,289 @@
+use crate::{ErrorKind, HighlightKind};
+use colored::Color;
+
+/// How a piece of text is displayed when colors are enabled.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub struct Style {
+    color: Option<Color>,
+    bold: bool,
+    dimmed: bool,
+    underline: bool,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Style::default()
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub fn dimmed(mut self) -> Self {
+        self.dimmed = true;
+        self
+    }
+
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    pub fn get_color(&self) -> Option<Color> {
+        self.color
+    }
+
+    pub fn is_bold(&self) -> bool {
+        self.bold
+    }
+
+    pub fn is_dimmed(&self) -> bool {
+        self.dimmed
+    }
+
+    pub fn is_underline(&self) -> bool {
+        self.underline
+    }
+
+    pub(crate) fn paint(&self, string: &str, enabled: bool) -> String {
+        let mut codes = Vec::new();
+        if self.bold {
+            codes.push("1".to_string());
+        }
+        if self.dimmed {
+            codes.push("2".to_string());
+        }
+        if self.underline {
+            codes.push("4".to_string());
+        }
+        if let Some(color) = self.color {
+            codes.push(color.to_fg_str().into_owned());
+        }
+        if !enabled || codes.is_empty() || string.is_empty() {
+            return string.to_string();
+        }
+        format!("\x1b[{}m{}\x1b[0m", codes.join(";"), string)
+    }
+}
+
+/// The styles used for each part of a rendered diagnostic.
+///
+/// `Theme::default()` follows the color scheme in `spec.md`. Themes only
+/// matter when colors are enabled; see [`ColorChoice`](crate::ColorChoice).
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Theme {
+    error: Style,
+    warning: Style,
+    improvement: Style,
+    code_style: Style,
+    highlight_error: Style,
+    highlight_warning: Style,
+    highlight_info: Style,
+    gutter: Style,
+    line_number: Style,
+    caption: Style,
+    dimmed: Style,
+}
+
+impl Theme {
+    pub fn new() -> Self {
+        Theme {
+            error: Style::new().color(Color::Red).bold(),
+            warning: Style::new().color(Color::Yellow).bold(),
+            improvement: Style::new().color(Color::Cyan).bold(),
+            code_style: Style::new().color(Color::Cyan).bold(),
+            highlight_error: Style::new().color(Color::Red).bold(),
+            highlight_warning: Style::new().color(Color::Yellow).bold(),
+            highlight_info: Style::new().color(Color::Blue).bold(),
+            gutter: Style::new().color(Color::Blue),
+            line_number: Style::new().color(Color::Blue),
+            caption: Style::new(),
+            dimmed: Style::new().color(Color::White).dimmed(),
+        }
+    }
+
+    /// Bright, bold colors for low-contrast terminals.
+    pub fn high_contrast() -> Self {
+        Theme {
+            error: Style::new().color(Color::BrightRed).bold(),
+            warning: Style::new().color(Color::BrightYellow).bold(),
+            improvement: Style::new().color(Color::BrightCyan).bold(),
+            code_style: Style::new().color(Color::BrightCyan).bold(),
+            highlight_error: Style::new().color(Color::BrightRed).bold(),
+            highlight_warning: Style::new().color(Color::BrightYellow).bold(),
+            highlight_info: Style::new().color(Color::BrightBlue).bold(),
+            gutter: Style::new().color(Color::BrightWhite).bold(),
+            line_number: Style::new().color(Color::BrightWhite).bold(),
+            caption: Style::new().color(Color::BrightWhite).bold(),
+            dimmed: Style::new().color(Color::White),
+        }
+    }
+
+    /// Avoids telling kinds apart by red and green alone.
+    pub fn colorblind_safe() -> Self {
+        Theme {
+            error: Style::new().color(Color::Magenta).bold(),
+            warning: Style::new().color(Color::Yellow).bold(),
+            improvement: Style::new().color(Color::Cyan).bold(),
+            code_style: Style::new().color(Color::Cyan).bold(),
+            highlight_error: Style::new().color(Color::Magenta).bold(),
+            highlight_warning: Style::new().color(Color::Yellow).bold(),
+            highlight_info: Style::new().color(Color::Blue).bold(),
+            gutter: Style::new().color(Color::Blue),
+            line_number: Style::new().color(Color::Blue),
+            caption: Style::new(),
+            dimmed: Style::new().color(Color::White).dimmed(),
+        }
+    }
+
+    /// No colors, only bold and underline.
+    pub fn monochrome() -> Self {
+        Theme {
+            error: Style::new().bold().underline(),
+            warning: Style::new().bold(),
+            improvement: Style::new().bold(),
+            code_style: Style::new().bold(),
+            highlight_error: Style::new().bold(),
+            highlight_warning: Style::new().bold(),
+            highlight_info: Style::new().bold(),
+            gutter: Style::new(),
+            line_number: Style::new(),
+            caption: Style::new().underline(),
+            dimmed: Style::new().dimmed(),
+        }
+    }
+
+    pub fn error_kind(mut self, kind: ErrorKind, style: Style) -> Self {
+        match kind {
+            ErrorKind::Error => self.error = style,
+            ErrorKind::Warning => self.warning = style,
+            ErrorKind::Improvement => self.improvement = style,
+            ErrorKind::CodeStyle => self.code_style = style,
+        }
+        self
+    }
+
+    pub fn highlight_kind(mut self, kind: HighlightKind, style: Style) -> Self {
+        match kind {
+            HighlightKind::Error => self.highlight_error = style,
+            HighlightKind::Warning => self.highlight_warning = style,
+            HighlightKind::Info => self.highlight_info = style,
+        }
+        self
+    }
+
+    pub fn gutter(mut self, style: Style) -> Self {
+        self.gutter = style;
+        self
+    }
+
+    pub fn line_number(mut self, style: Style) -> Self {
+        self.line_number = style;
+        self
+    }
+
+    pub fn caption(mut self, style: Style) -> Self {
+        self.caption = style;
+        self
+    }
+
+    /// Style for de-emphasized text, such as the file location of a snippet.
+    pub fn dimmed(mut self, style: Style) -> Self {
+        self.dimmed = style;
+        self
+    }
+
+    pub fn get_error_kind(&self, kind: ErrorKind) -> Style {
+        match kind {
+            ErrorKind::Error => self.error,
+            ErrorKind::Warning => self.warning,
+            ErrorKind::Improvement => self.improvement,
+            ErrorKind::CodeStyle => self.code_style,
+        }
+    }
+
+    pub fn get_highlight_kind(&self, kind: HighlightKind) -> Style {
+        match kind {
+            HighlightKind::Error => self.highlight_error,
+            HighlightKind::Warning => self.highlight_warning,
+            HighlightKind::Info => self.highlight_info,
+        }
+    }
+
+    pub fn get_gutter(&self) -> Style {
+        self.gutter
+    }
+
+    pub fn get_line_number(&self) -> Style {
+        self.line_number
+    }
+
+    pub fn get_caption(&self) -> Style {
+        self.caption
+    }
+
+    pub fn get_dimmed(&self) -> Style {
+        self.dimmed
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn paint_test() {
+        let style = Style::new().color(Color::Red).bold();
+        assert_eq!(style.paint("hello", true), "\x1b[1;31mhello\x1b[0m");
+        assert_eq!(style.paint("hello", false), "hello");
+        assert_eq!(style.paint("", true), "");
+        assert_eq!(Style::new().paint("hello", true), "hello");
+        assert_eq!(
+            Style::new().dimmed().underline().paint("hello", true),
+            "\x1b[2;4mhello\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn theme_overrides_test() {
+        let style = Style::new().color(Color::Green);
+        let theme = Theme::new()
+            .error_kind(ErrorKind::Warning, style)
+            .highlight_kind(HighlightKind::Info, style)
+            .gutter(style);
+        assert_eq!(theme.get_error_kind(ErrorKind::Warning), style);
+        assert_eq!(theme.get_highlight_kind(HighlightKind::Info), style);
+        assert_eq!(theme.get_gutter(), style);
+        assert_eq!(
+            theme.get_error_kind(ErrorKind::Error),
+            Theme::new().get_error_kind(ErrorKind::Error)
+        );
+    }
+
+    #[test]
+    fn monochrome_has_no_colors() {
+        let theme = Theme::monochrome();
+        let kinds = [
+            ErrorKind::Error,
+            ErrorKind::Warning,
+            ErrorKind::Improvement,
+            ErrorKind::CodeStyle,
+        ];
+        for kind in kinds {
+            assert_eq!(theme.get_error_kind(kind).get_color(), None);
+        }
+        assert_eq!(theme.get_gutter().get_color(), None);
+    }
+}